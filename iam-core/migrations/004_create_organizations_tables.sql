@@ -15,5 +15,5 @@ CREATE TABLE IF NOT EXISTS organizations (
 CREATE TABLE user_organizations (
     user_id CHAR(36) NOT NULL,
     organization_id CHAR(36) NOT NULL,
-    PRIMARY KEY (user_id, organization_id),
+    PRIMARY KEY (user_id, organization_id)
 );
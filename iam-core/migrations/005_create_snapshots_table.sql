@@ -0,0 +1,11 @@
+-- 创建聚合快照表（每个聚合只保留最新快照）
+CREATE TABLE IF NOT EXISTS snapshots (
+    aggregate_id BINARY(16) PRIMARY KEY,
+    aggregate_type VARCHAR(100) NOT NULL,
+    version BIGINT UNSIGNED NOT NULL,
+    state JSON NOT NULL,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6)
+);
+
+-- 创建索引
+CREATE INDEX idx_snapshots_aggregate_type ON snapshots (aggregate_type);
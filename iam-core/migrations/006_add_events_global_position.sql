@@ -0,0 +1,13 @@
+-- 为事件表增加全局顺序位置，供投影和下游集成按位置追赶
+ALTER TABLE events ADD COLUMN position BIGINT UNSIGNED NULL;
+
+-- 按写入顺序为已有事件编号
+SET @position := 0;
+UPDATE events SET position = (@position := @position + 1) ORDER BY created_at, aggregate_id, sequence;
+
+ALTER TABLE events
+    MODIFY COLUMN position BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
+    ADD UNIQUE KEY uq_events_position (position);
+
+-- 创建索引
+CREATE INDEX idx_events_type_position ON events (event_type, position);
@@ -0,0 +1,7 @@
+-- 为事件增加元数据信封（操作人、租户、关联ID、因果ID、客户端IP），用于审计追踪
+ALTER TABLE events ADD COLUMN metadata JSON NULL;
+
+-- 历史事件没有元数据，填充为空对象
+UPDATE events SET metadata = JSON_OBJECT() WHERE metadata IS NULL;
+
+ALTER TABLE events MODIFY COLUMN metadata JSON NOT NULL;
@@ -0,0 +1,2 @@
+-- 为事件记录负载的结构版本，读取时由 upcaster 将旧版本负载升级到当前结构
+ALTER TABLE events ADD COLUMN schema_version INT UNSIGNED NOT NULL DEFAULT 1;
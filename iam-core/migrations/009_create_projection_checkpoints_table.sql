@@ -0,0 +1,6 @@
+-- 创建投影检查点表，记录每个投影已处理到的事件全局位置
+CREATE TABLE IF NOT EXISTS projection_checkpoints (
+    projector VARCHAR(255) NOT NULL PRIMARY KEY,
+    position BIGINT UNSIGNED NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6)
+);
@@ -0,0 +1,17 @@
+-- 创建角色读模型表
+-- 已删除的角色保留并标记为删除，角色代码在删除后可以被重新使用，因此不设唯一约束
+CREATE TABLE IF NOT EXISTS roles_view (
+    id BINARY(16) PRIMARY KEY,
+    tenant_id BINARY(16) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    code VARCHAR(255) NOT NULL,
+    description TEXT NULL,
+    deleted BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6),
+    deleted_at TIMESTAMP(6) NULL
+);
+
+-- 创建索引
+CREATE INDEX idx_roles_tenant_code ON roles_view (tenant_id, code);
+CREATE INDEX idx_roles_tenant_deleted ON roles_view (tenant_id, deleted);
@@ -0,0 +1,2 @@
+-- 为用户读模型增加锁定到期时间，为空且状态为 locked 时表示需要手动解锁
+ALTER TABLE users_view ADD COLUMN locked_until TIMESTAMP(6) NULL AFTER status;
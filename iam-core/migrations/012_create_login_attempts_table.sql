@@ -0,0 +1,9 @@
+-- 创建登录失败计数表，按用户（user:<id>）和客户端IP（ip:<addr>）分别计数
+CREATE TABLE IF NOT EXISTS login_attempts (
+    subject VARCHAR(255) NOT NULL PRIMARY KEY,
+    failed_count INT UNSIGNED NOT NULL DEFAULT 0,
+    window_started_at TIMESTAMP(6) NULL,
+    lockouts INT UNSIGNED NOT NULL DEFAULT 0,
+    blocked_until TIMESTAMP(6) NULL,
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6)
+);
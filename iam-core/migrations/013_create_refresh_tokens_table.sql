@@ -0,0 +1,17 @@
+-- 创建刷新令牌表，只保存令牌的 SHA-256 哈希
+-- 同一次登录轮换出的令牌属于同一家族，重复使用已轮换的令牌时整个家族被吊销
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    token_hash CHAR(64) NOT NULL PRIMARY KEY,
+    family_id BINARY(16) NOT NULL,
+    user_id BINARY(16) NOT NULL,
+    tenant_id BINARY(16) NOT NULL,
+    expires_at TIMESTAMP(6) NOT NULL,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    rotated_at TIMESTAMP(6) NULL,
+    revoked_at TIMESTAMP(6) NULL
+);
+
+-- 创建索引
+CREATE INDEX idx_refresh_tokens_family_id ON refresh_tokens (family_id);
+CREATE INDEX idx_refresh_tokens_user_id ON refresh_tokens (user_id);
+CREATE INDEX idx_refresh_tokens_expires_at ON refresh_tokens (expires_at);
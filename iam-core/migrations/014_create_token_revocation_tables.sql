@@ -0,0 +1,18 @@
+-- 创建访问令牌吊销表，按 jti 吊销单个令牌，令牌过期后记录即可删除
+CREATE TABLE IF NOT EXISTS revoked_tokens (
+    jti VARCHAR(64) NOT NULL PRIMARY KEY,
+    expires_at TIMESTAMP(6) NOT NULL,
+    revoked_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6)
+);
+
+-- 创建用户令牌吊销表，吊销用户在 issued_before 及之前签发的所有令牌
+CREATE TABLE IF NOT EXISTS user_token_revocations (
+    user_id BINARY(16) NOT NULL PRIMARY KEY,
+    issued_before TIMESTAMP(6) NOT NULL,
+    expires_at TIMESTAMP(6) NOT NULL,
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6)
+);
+
+-- 创建索引，用于清理过期记录
+CREATE INDEX idx_revoked_tokens_expires_at ON revoked_tokens (expires_at);
+CREATE INDEX idx_user_token_revocations_expires_at ON user_token_revocations (expires_at);
@@ -0,0 +1,23 @@
+-- 组织表改为由投影维护的读模型，ID 与其他表一致使用 BINARY(16)
+-- path 为从根到当前组织的物化路径（以 / 分隔的无连字符 UUID），用于查询子树和移动子树
+-- 组织代码的唯一性由接口检查，不设唯一约束，避免并发创建的重复代码阻塞投影
+ALTER TABLE organizations
+    DROP INDEX tenant_id,
+    MODIFY id BINARY(16) NOT NULL,
+    MODIFY tenant_id BINARY(16) NOT NULL,
+    MODIFY parent_id BINARY(16) NULL,
+    ADD COLUMN path VARCHAR(2048) CHARACTER SET ascii NOT NULL DEFAULT '' AFTER level,
+    MODIFY created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    MODIFY updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6);
+
+CREATE INDEX idx_organizations_tenant_code ON organizations (tenant_id, code);
+CREATE INDEX idx_organizations_tenant_path ON organizations (tenant_id, path);
+CREATE INDEX idx_organizations_parent_id ON organizations (parent_id);
+
+-- 用户-组织关联表同样由投影维护
+ALTER TABLE user_organizations
+    MODIFY user_id BINARY(16) NOT NULL,
+    MODIFY organization_id BINARY(16) NOT NULL,
+    ADD COLUMN created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6);
+
+CREATE INDEX idx_user_organizations_organization_id ON user_organizations (organization_id);
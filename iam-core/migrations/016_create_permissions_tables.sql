@@ -0,0 +1,30 @@
+-- 创建权限读模型表，权限目录为平台级，不区分租户
+-- 已删除的权限保留并标记为删除，权限代码的唯一性由接口检查，不设唯一约束
+CREATE TABLE IF NOT EXISTS permissions_view (
+    id BINARY(16) PRIMARY KEY,
+    parent_id BINARY(16) NULL,
+    permission_type VARCHAR(20) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    code VARCHAR(255) NOT NULL,
+    route VARCHAR(1024) NULL,
+    icon VARCHAR(255) NULL,
+    description TEXT NULL,
+    deleted BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    updated_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6) ON UPDATE CURRENT_TIMESTAMP(6),
+    deleted_at TIMESTAMP(6) NULL
+);
+
+CREATE INDEX idx_permissions_code ON permissions_view (code);
+CREATE INDEX idx_permissions_parent_id ON permissions_view (parent_id);
+CREATE INDEX idx_permissions_type_deleted ON permissions_view (permission_type, deleted);
+
+-- 角色-权限关联表，由投影维护
+CREATE TABLE IF NOT EXISTS role_permissions (
+    role_id BINARY(16) NOT NULL,
+    permission_id BINARY(16) NOT NULL,
+    created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    PRIMARY KEY (role_id, permission_id)
+);
+
+CREATE INDEX idx_role_permissions_permission_id ON role_permissions (permission_id);
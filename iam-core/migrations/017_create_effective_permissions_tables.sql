@@ -0,0 +1,37 @@
+-- 用户有效权限读模型，由有效权限投影维护
+-- 每行记录用户通过某个角色的某项授权获得的一项权限，授予菜单时其下级权限一并获得
+CREATE TABLE IF NOT EXISTS user_effective_permissions (
+    user_id BINARY(16) NOT NULL,
+    permission_id BINARY(16) NOT NULL,
+    role_id BINARY(16) NOT NULL,
+    granted_permission_id BINARY(16) NOT NULL,
+    code VARCHAR(255) NOT NULL,
+    PRIMARY KEY (user_id, permission_id, role_id, granted_permission_id)
+);
+
+CREATE INDEX idx_user_effective_permissions_user_code ON user_effective_permissions (user_id, code);
+CREATE INDEX idx_user_effective_permissions_permission_id ON user_effective_permissions (permission_id);
+
+-- 以下三张表是有效权限投影自己维护的状态，不依赖其他投影的进度，重建时一并重建
+-- 权限树，path 为从根到当前权限的物化路径（以 / 分隔的无连字符 UUID）
+CREATE TABLE IF NOT EXISTS authz_permissions (
+    id BINARY(16) PRIMARY KEY,
+    code VARCHAR(255) NOT NULL,
+    path VARCHAR(2048) CHARACTER SET ascii NOT NULL
+);
+
+CREATE INDEX idx_authz_permissions_path ON authz_permissions (path);
+
+CREATE TABLE IF NOT EXISTS authz_role_permissions (
+    role_id BINARY(16) NOT NULL,
+    permission_id BINARY(16) NOT NULL,
+    PRIMARY KEY (role_id, permission_id)
+);
+
+CREATE TABLE IF NOT EXISTS authz_user_roles (
+    user_id BINARY(16) NOT NULL,
+    role_id BINARY(16) NOT NULL,
+    PRIMARY KEY (user_id, role_id)
+);
+
+CREATE INDEX idx_authz_user_roles_role_id ON authz_user_roles (role_id);
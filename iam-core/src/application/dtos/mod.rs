@@ -1,3 +1,10 @@
+pub mod role_view;
+pub mod tenant_view;
+pub mod organization_view;
+pub mod organization_member_view;
+pub mod permission_view;
+pub mod role_permission_view;
+
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +29,7 @@ pub struct Model {
     pub email: String,
     pub password_hash: String,
     pub status: String,
+    pub locked_until: Option<ChronoDateTimeUtc>,
     pub created_at: ChronoDateTimeUtc,
     pub updated_at: ChronoDateTimeUtc,
 }
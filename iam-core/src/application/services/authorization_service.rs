@@ -0,0 +1,31 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::infrastructure::persistence::effective_permissions::EffectivePermissionStore;
+use crate::error::AppError;
+
+/// Answers what a user is allowed to do from the effective permission read model.
+///
+/// A user holds a permission when one of their roles is granted it or a menu above it.
+/// The read model is eventually consistent, so a grant or revocation takes effect once
+/// the effective permission projection has caught up with it.
+pub struct AuthorizationService {
+    permissions: Arc<dyn EffectivePermissionStore>,
+}
+
+impl AuthorizationService {
+    pub fn new(permissions: Arc<dyn EffectivePermissionStore>) -> Self {
+        Self { permissions }
+    }
+
+    /// Whether the user holds the permission with `code`.
+    pub async fn has_permission(&self, user_id: Uuid, code: &str) -> Result<bool, AppError> {
+        Ok(!self.permissions.find_user_permission(user_id, code).await?.is_empty())
+    }
+
+    /// The codes of every permission the user holds.
+    pub async fn permissions_for(&self, user_id: Uuid) -> Result<BTreeSet<String>, AppError> {
+        let permissions = self.permissions.find_user_permissions(user_id).await?;
+        Ok(permissions.into_iter().map(|permission| permission.code).collect())
+    }
+}
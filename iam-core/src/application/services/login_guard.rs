@@ -0,0 +1,160 @@
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::application::services::user_service::UserService;
+use crate::domain::identity_access::aggregates::UserStatus;
+use crate::domain::identity_access::commands::LockUserCommand;
+use crate::infrastructure::persistence::event_store::EventMetadata;
+use crate::infrastructure::persistence::login_attempts::{LoginAttemptStore, LoginAttempts};
+use crate::error::AppError;
+
+/// Thresholds for failed logins and how long crossing them locks the subject out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoginProtectionPolicy {
+    /// Failures within `window` that lock the user's account.
+    pub max_failed_per_user: u32,
+    /// Failures within `window` that block the client IP.
+    pub max_failed_per_ip: u32,
+    pub window: Duration,
+    /// Length of the first lockout; each further lockout doubles it.
+    pub base_lockout: Duration,
+    pub max_lockout: Duration,
+}
+
+impl LoginProtectionPolicy {
+    /// Length of the `lockouts`-th lockout in a row, capped at `max_lockout`.
+    pub fn lockout_duration(&self, lockouts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(lockouts.saturating_sub(1));
+        self.base_lockout.saturating_mul(factor).min(self.max_lockout)
+    }
+}
+
+/// Tracks failed logins per user and per client IP and locks them out when they fail too often.
+///
+/// Users are locked through their aggregate, so each lockout is recorded in the event
+/// store like any other lock. Client IPs are only blocked in the attempt store.
+pub struct LoginGuard {
+    attempts: Arc<dyn LoginAttemptStore>,
+    users: Arc<UserService>,
+    policy: LoginProtectionPolicy,
+}
+
+impl LoginGuard {
+    pub fn new(attempts: Arc<dyn LoginAttemptStore>, users: Arc<UserService>, policy: LoginProtectionPolicy) -> Self {
+        Self { attempts, users, policy }
+    }
+
+    /// Rejects login attempts from a client IP that is blocked.
+    pub async fn check_client(&self, client_ip: Option<&str>) -> Result<(), AppError> {
+        let Some(client_ip) = client_ip else {
+            return Ok(());
+        };
+
+        let attempts = self.attempts.load_attempts(&ip_subject(client_ip)).await?;
+        match attempts.blocked_until {
+            Some(until) if until > Utc::now() => Err(AppError::RateLimited(format!(
+                "Too many failed login attempts, try again after {}",
+                until.to_rfc3339()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Rejects logins to a locked or deactivated account.
+    ///
+    /// Reads the user aggregate rather than the read model, so an account locked a moment ago
+    /// is refused even before the projection has caught up.
+    pub async fn check_user(&self, user_id: Uuid) -> Result<(), AppError> {
+        let user = match self.users.get_user(user_id).await {
+            Ok(user) => user,
+            Err(AppError::AggregateNotFound(_)) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        if user.is_locked_at(Utc::now()) {
+            return Err(AppError::AccountLocked(match user.locked_until() {
+                Some(until) => format!("Account is locked until {}", until.to_rfc3339()),
+                None => "Account is locked".to_string(),
+            }));
+        }
+        if *user.status() == UserStatus::Inactive {
+            return Err(AppError::AuthenticationError("Invalid username or password".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Counts a failed login for the client IP in `metadata` and for `user_id`, if the username
+    /// matched a user. Returns `AccountLocked` when this failure locked the user's account.
+    pub async fn record_failure(&self, user_id: Option<Uuid>, metadata: &EventMetadata) -> Result<(), AppError> {
+        let now = Utc::now();
+
+        if let Some(ref client_ip) = metadata.client_ip {
+            let subject = ip_subject(client_ip);
+            let attempts = self.attempts.record_failure(&subject, now, self.window_start(now)).await?;
+            if attempts.failed_count >= self.policy.max_failed_per_ip {
+                let until = self.lock_out(&subject, &attempts, now).await?;
+                tracing::warn!("Blocked logins from {} until {} after {} failed attempts", client_ip, until, attempts.failed_count);
+            }
+        }
+
+        let Some(user_id) = user_id else {
+            return Ok(());
+        };
+
+        let subject = user_subject(user_id);
+        let attempts = self.attempts.record_failure(&subject, now, self.window_start(now)).await?;
+        if attempts.failed_count < self.policy.max_failed_per_user {
+            return Ok(());
+        }
+
+        let until = self.lock_out(&subject, &attempts, now).await?;
+        let command = LockUserCommand {
+            user_id,
+            reason: format!("{} failed login attempts", attempts.failed_count),
+            locked_until: Some(until),
+            locked_by: None,
+        };
+        match self.users.lock_user(command, metadata).await {
+            Ok(()) => {}
+            // The account was locked or deactivated meanwhile; either way the login is refused.
+            Err(AppError::DomainError(e)) => tracing::warn!("Could not lock user {} after failed logins: {}", user_id, e),
+            Err(e) => return Err(e),
+        }
+
+        Err(AppError::AccountLocked(format!("Account is locked until {}", until.to_rfc3339())))
+    }
+
+    /// Clears the failed logins counted for the user.
+    pub async fn record_success(&self, user_id: Uuid) -> Result<(), AppError> {
+        self.attempts.reset(&user_subject(user_id)).await
+    }
+
+    fn window_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - chrono::Duration::from_std(self.policy.window).unwrap_or(chrono::Duration::MAX)
+    }
+
+    /// Records the next lockout of `subject` and returns when it ends. A subject that stayed
+    /// clean for a whole window after its last lockout starts again from the base duration.
+    async fn lock_out(&self, subject: &str, attempts: &LoginAttempts, now: DateTime<Utc>) -> Result<DateTime<Utc>, AppError> {
+        let previous = match attempts.blocked_until {
+            Some(until) if until >= self.window_start(now) => attempts.lockouts,
+            _ => 0,
+        };
+        let lockouts = previous + 1;
+        let duration = chrono::Duration::from_std(self.policy.lockout_duration(lockouts))
+            .map_err(|e| AppError::InternalError(format!("Invalid lockout duration: {}", e)))?;
+        let until = now + duration;
+
+        self.attempts.record_lockout(subject, lockouts, until).await?;
+        Ok(until)
+    }
+}
+
+fn user_subject(user_id: Uuid) -> String {
+    format!("user:{}", user_id)
+}
+
+fn ip_subject(client_ip: &str) -> String {
+    format!("ip:{}", client_ip)
+}
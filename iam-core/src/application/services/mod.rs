@@ -1,7 +1,25 @@
 pub mod user_service;
 pub mod role_service;
+pub mod tenant_service;
+pub mod organization_service;
+pub mod permission_service;
+pub mod authorization_service;
+pub mod route_permission_service;
+pub mod policy_decision_service;
 pub mod query_service;
+pub mod login_guard;
+pub mod refresh_token_service;
+pub mod platform_bootstrap;
 
 pub use user_service::*;
 pub use role_service::*;
-pub use query_service::*;
\ No newline at end of file
+pub use tenant_service::*;
+pub use organization_service::*;
+pub use permission_service::*;
+pub use authorization_service::*;
+pub use route_permission_service::*;
+pub use policy_decision_service::*;
+pub use query_service::*;
+pub use login_guard::*;
+pub use refresh_token_service::*;
+pub use platform_bootstrap::*;
\ No newline at end of file
@@ -0,0 +1,238 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tokio::sync::OwnedMutexGuard;
+use uuid::Uuid;
+use crate::domain::identity_access::aggregates::organization::Organization;
+use crate::domain::identity_access::aggregates::user::User;
+use crate::domain::identity_access::commands::{
+    CreateOrganizationCommand, RenameOrganizationCommand, MoveOrganizationCommand, DeleteOrganizationCommand,
+    AssignUserOrganizationCommand, RemoveUserOrganizationCommand
+};
+use crate::domain::identity_access::events::IdentityAccessEvent;
+use crate::infrastructure::persistence::event_store::{EventMetadata, EventStore, deserialize_events};
+use crate::infrastructure::persistence::repository::{EventSourcedRepository, RetryPolicy};
+use crate::infrastructure::persistence::snapshot_store::SnapshotPolicy;
+use crate::error::AppError;
+use anyhow::Result;
+
+/// How many organization events are read at a time when looking for child organizations.
+const CHILD_SCAN_BATCH_SIZE: u64 = 500;
+
+pub struct OrganizationService {
+    event_store: Arc<dyn EventStore>,
+    organizations: EventSourcedRepository<Organization>,
+    users: EventSourcedRepository<User>,
+    /// Changes to the shape of a tenant's tree are checked against other organizations' streams,
+    /// which optimistic concurrency on the changed organization alone does not protect. They are
+    /// serialized per tenant, so each one sees the tree as the previous one left it.
+    tree_locks: Mutex<HashMap<Uuid, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl OrganizationService {
+    pub fn new(event_store: Arc<dyn EventStore>) -> Self {
+        Self {
+            event_store: event_store.clone(),
+            organizations: EventSourcedRepository::new(event_store.clone()),
+            users: EventSourcedRepository::new(event_store),
+            tree_locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Restores organizations and users from their latest snapshot and writes new snapshots according to `policy`.
+    pub fn with_snapshots(mut self, policy: SnapshotPolicy) -> Self {
+        self.organizations = self.organizations.with_snapshots(policy.clone());
+        self.users = self.users.with_snapshots(policy);
+        self
+    }
+
+    /// Re-runs commands that lose a concurrency race according to `policy`.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.organizations = self.organizations.with_retry(policy);
+        self.users = self.users.with_retry(policy);
+        self
+    }
+
+    pub async fn create_organization(&self, command: CreateOrganizationCommand, metadata: &EventMetadata) -> Result<Uuid, AppError> {
+        // Keeps the parent from being deleted while the child is added under it
+        let _tree = self.lock_tree(command.tenant_id).await;
+        let organization_id = Uuid::new_v4();
+        let parent = match command.parent_id {
+            Some(parent_id) => Some(self.get_organization(command.tenant_id, parent_id).await?),
+            None => None,
+        };
+
+        // 1. Execute business logic on the aggregate.
+        let event = Organization::create(
+            organization_id,
+            command.tenant_id,
+            parent.as_ref(),
+            command.name,
+            command.code,
+        )
+        .map_err(|e| AppError::DomainError(e.to_string()))?;
+
+        // 2. Save the new event to the event store.
+        self.organizations.save(&mut Organization::default(), &[event], metadata).await?;
+
+        Ok(organization_id)
+    }
+
+    pub async fn rename_organization(&self, command: RenameOrganizationCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.get_organization(command.tenant_id, command.organization_id).await?;
+
+        self.organizations.execute(command.organization_id, metadata, |organization| {
+            let event = organization.rename(command.name.clone())
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
+
+    pub async fn move_organization(&self, command: MoveOrganizationCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        let _tree = self.lock_tree(command.tenant_id).await;
+        self.get_organization(command.tenant_id, command.organization_id).await?;
+
+        // The new parent and its ancestors are loaded up front so the aggregate can reject cycles;
+        // the tree lock keeps them from changing until the move is saved
+        let (parent, ancestors) = match command.parent_id {
+            Some(parent_id) => {
+                let parent = self.get_organization(command.tenant_id, parent_id).await?;
+                let ancestors = self.ancestors(&parent).await?;
+                (Some(parent), ancestors)
+            }
+            None => (None, Vec::new()),
+        };
+
+        self.organizations.execute(command.organization_id, metadata, |organization| {
+            let event = organization.move_to(parent.as_ref(), &ancestors)
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
+
+    /// Deletes an organization that has no child organizations left.
+    pub async fn delete_organization(&self, command: DeleteOrganizationCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        let _tree = self.lock_tree(command.tenant_id).await;
+        self.get_organization(command.tenant_id, command.organization_id).await?;
+        if self.has_children(command.organization_id).await? {
+            return Err(AppError::DomainError("Cannot delete an organization that has child organizations".to_string()));
+        }
+
+        self.organizations.execute(command.organization_id, metadata, |organization| {
+            let event = organization.delete()
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
+
+    pub async fn assign_user_organization(&self, command: AssignUserOrganizationCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        // Memberships live in the user's stream; the organization is only loaded to check it can be joined
+        let organization = self.get_organization(command.tenant_id, command.organization_id).await?;
+        if organization.is_deleted() {
+            return Err(AppError::DomainError("Cannot assign users to a deleted organization".to_string()));
+        }
+
+        self.users.execute(command.user_id, metadata, |user| {
+            if user.tenant_id() != organization.tenant_id() {
+                return Err(AppError::DomainError("User belongs to a different tenant".to_string()));
+            }
+
+            let event = user.assign_organization(command.organization_id)
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_user_organization(&self, command: RemoveUserOrganizationCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.get_organization(command.tenant_id, command.organization_id).await?;
+
+        self.users.execute(command.user_id, metadata, |user| {
+            let event = user.remove_organization(command.organization_id)
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
+
+    /// Loads an organization of the tenant; organizations of other tenants are reported as missing.
+    pub async fn get_organization(&self, tenant_id: Uuid, organization_id: Uuid) -> Result<Organization, AppError> {
+        let organization = self.organizations.load(organization_id).await?;
+        if organization.tenant_id() != tenant_id {
+            return Err(AppError::AggregateNotFound(format!("Organization {}", organization_id)));
+        }
+        Ok(organization)
+    }
+
+    /// Waits until no other change to the shape of the tenant's tree is in progress.
+    async fn lock_tree(&self, tenant_id: Uuid) -> OwnedMutexGuard<()> {
+        let lock = self.tree_locks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(tenant_id)
+            .or_default()
+            .clone();
+        lock.lock_owned().await
+    }
+
+    /// Whether an organization that is not deleted sits directly under `organization_id`.
+    ///
+    /// The event store is the source of truth here, as the read model may not show a child
+    /// added moments ago: every organization ever placed under it is a candidate, and its
+    /// current state decides. Deleting is rare enough to afford the scan.
+    async fn has_children(&self, organization_id: Uuid) -> Result<bool, AppError> {
+        let mut candidates = HashSet::new();
+        let mut position = 0;
+        loop {
+            let stored_events = self.event_store
+                .read_all_by_type(&["OrganizationCreated", "OrganizationMoved"], position, CHILD_SCAN_BATCH_SIZE)
+                .await?;
+            let Some(last) = stored_events.last() else {
+                break;
+            };
+            position = last.position;
+
+            for event in deserialize_events(&stored_events)? {
+                match event {
+                    IdentityAccessEvent::OrganizationCreated(e) if e.parent_id == Some(organization_id) => {
+                        candidates.insert(e.organization_id);
+                    }
+                    IdentityAccessEvent::OrganizationMoved(e) if e.parent_id == Some(organization_id) => {
+                        candidates.insert(e.organization_id);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for candidate in candidates {
+            let child = self.organizations.load(candidate).await?;
+            if child.parent_id() == Some(organization_id) && !child.is_deleted() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns the ids of every organization above `organization`, nearest first.
+    async fn ancestors(&self, organization: &Organization) -> Result<Vec<Uuid>, AppError> {
+        let mut ancestors = Vec::new();
+        let mut seen = HashSet::new();
+        let mut next = organization.parent_id();
+        while let Some(id) = next {
+            if !seen.insert(id) {
+                return Err(AppError::InternalError(format!("Organization tree contains a cycle at {}", id)));
+            }
+            ancestors.push(id);
+            next = self.organizations.load(id).await?.parent_id();
+        }
+        Ok(ancestors)
+    }
+}
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::domain::identity_access::aggregates::permission::Permission;
+use crate::domain::identity_access::commands::{
+    CreatePermissionCommand, UpdatePermissionCommand, DeletePermissionCommand
+};
+use crate::infrastructure::persistence::event_store::{EventMetadata, EventStore};
+use crate::infrastructure::persistence::repository::{EventSourcedRepository, RetryPolicy};
+use crate::infrastructure::persistence::snapshot_store::SnapshotPolicy;
+use crate::error::AppError;
+use anyhow::Result;
+
+pub struct PermissionService {
+    permissions: EventSourcedRepository<Permission>,
+}
+
+impl PermissionService {
+    pub fn new(event_store: Arc<dyn EventStore>) -> Self {
+        Self {
+            permissions: EventSourcedRepository::new(event_store),
+        }
+    }
+
+    /// Restores permissions from their latest snapshot and writes new snapshots according to `policy`.
+    pub fn with_snapshots(mut self, policy: SnapshotPolicy) -> Self {
+        self.permissions = self.permissions.with_snapshots(policy);
+        self
+    }
+
+    /// Re-runs commands that lose a concurrency race according to `policy`.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.permissions = self.permissions.with_retry(policy);
+        self
+    }
+
+    pub async fn create_permission(&self, command: CreatePermissionCommand, metadata: &EventMetadata) -> Result<Uuid, AppError> {
+        let permission_id = Uuid::new_v4();
+        let parent = match command.parent_id {
+            Some(parent_id) => Some(self.permissions.load(parent_id).await?),
+            None => None,
+        };
+
+        // 1. Execute business logic on the aggregate.
+        let event = Permission::create(
+            permission_id,
+            parent.as_ref(),
+            &command.permission_type,
+            command.name,
+            command.code,
+            command.route,
+            command.icon,
+            command.description,
+        )
+        .map_err(|e| AppError::DomainError(e.to_string()))?;
+
+        // 2. Save the new event to the event store.
+        self.permissions.save(&mut Permission::default(), &[event], metadata).await?;
+
+        Ok(permission_id)
+    }
+
+    pub async fn update_permission(&self, command: UpdatePermissionCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.permissions.execute(command.permission_id, metadata, |permission| {
+            let event = permission.update(
+                command.name.clone(),
+                command.route.clone(),
+                command.icon.clone(),
+                command.description.clone(),
+            )
+            .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
+
+    /// Deletes a permission; callers check that no other permission is nested under it.
+    pub async fn delete_permission(&self, command: DeletePermissionCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.permissions.execute(command.permission_id, metadata, |permission| {
+            let event = permission.delete()
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
+
+    pub async fn get_permission(&self, permission_id: Uuid) -> Result<Permission, AppError> {
+        self.permissions.load(permission_id).await
+    }
+}
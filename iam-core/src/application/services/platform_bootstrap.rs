@@ -0,0 +1,134 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::domain::identity_access::aggregates::Aggregate;
+use crate::domain::identity_access::aggregates::permission::Permission;
+use crate::domain::identity_access::aggregates::role::Role;
+use crate::domain::identity_access::aggregates::user::User;
+use crate::domain::identity_access::events::IdentityAccessEvent;
+use crate::infrastructure::persistence::event_store::{EventMetadata, EventStore};
+use crate::infrastructure::persistence::repository::EventSourcedRepository;
+use crate::error::AppError;
+use anyhow::Result;
+
+/// The permission that makes a user of the platform tenant a platform administrator.
+pub const PLATFORM_ADMIN_PERMISSION: &str = "platform:admin";
+
+/// Fixed ids of what the bootstrap creates, so that every start finds what an earlier one created.
+pub const PLATFORM_ADMIN_PERMISSION_ID: Uuid = Uuid::from_u128(0x5a1f7e0c_0000_4000_8000_000000000001);
+pub const PLATFORM_ADMIN_ROLE_ID: Uuid = Uuid::from_u128(0x5a1f7e0c_0000_4000_8000_000000000002);
+pub const PLATFORM_ADMIN_USER_ID: Uuid = Uuid::from_u128(0x5a1f7e0c_0000_4000_8000_000000000003);
+
+/// The first platform administrator, created from configuration when the platform starts.
+pub struct PlatformAdminAccount {
+    pub username: String,
+    pub email: String,
+    pub password_hash: String,
+}
+
+/// Creates the platform:admin permission, a platform tenant role holding it and the first
+/// platform administrator, who can then grant the role to others.
+pub struct PlatformBootstrap {
+    permissions: EventSourcedRepository<Permission>,
+    roles: EventSourcedRepository<Role>,
+    users: EventSourcedRepository<User>,
+}
+
+impl PlatformBootstrap {
+    pub fn new(event_store: Arc<dyn EventStore>) -> Self {
+        Self {
+            permissions: EventSourcedRepository::new(event_store.clone()),
+            roles: EventSourcedRepository::new(event_store.clone()),
+            users: EventSourcedRepository::new(event_store),
+        }
+    }
+
+    /// Makes sure the administrator exists and holds the platform administrator role, creating
+    /// whatever is missing. An existing administrator keeps their password.
+    ///
+    /// Returns whether the administrator account was created.
+    pub async fn ensure_admin(&self, tenant_id: Uuid, account: PlatformAdminAccount, metadata: &EventMetadata) -> Result<bool, AppError> {
+        if load_or_none(&self.permissions, PLATFORM_ADMIN_PERMISSION_ID).await?.is_none() {
+            let event = Permission::create(
+                PLATFORM_ADMIN_PERMISSION_ID,
+                None,
+                "menu",
+                "Platform administration".to_string(),
+                PLATFORM_ADMIN_PERMISSION.to_string(),
+                None,
+                None,
+                Some("Manages tenants, the permission catalog and every tenant's access".to_string()),
+            )
+            .map_err(|e| AppError::DomainError(e.to_string()))?;
+            create(&self.permissions, event, metadata).await?;
+        }
+
+        if load_or_none(&self.roles, PLATFORM_ADMIN_ROLE_ID).await?.is_none() {
+            let event = Role::create(
+                PLATFORM_ADMIN_ROLE_ID,
+                tenant_id,
+                "Platform administrator".to_string(),
+                "platform_admin".to_string(),
+                None,
+            )
+            .map_err(|e| AppError::DomainError(e.to_string()))?;
+            create(&self.roles, event, metadata).await?;
+        }
+        self.roles.execute(PLATFORM_ADMIN_ROLE_ID, metadata, |role| {
+            if role.permissions().contains(&PLATFORM_ADMIN_PERMISSION_ID) {
+                return Ok(vec![]);
+            }
+            let event = role.grant_permission(PLATFORM_ADMIN_PERMISSION_ID)
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        let created = match load_or_none(&self.users, PLATFORM_ADMIN_USER_ID).await? {
+            Some(_) => false,
+            None => {
+                let event = User::register(
+                    PLATFORM_ADMIN_USER_ID,
+                    tenant_id,
+                    account.username,
+                    account.email,
+                    account.password_hash,
+                )
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+                create(&self.users, event, metadata).await?
+            }
+        };
+        self.users.execute(PLATFORM_ADMIN_USER_ID, metadata, |user| {
+            if user.roles().contains(&PLATFORM_ADMIN_ROLE_ID) {
+                return Ok(vec![]);
+            }
+            let event = user.assign_role(PLATFORM_ADMIN_ROLE_ID)
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(created)
+    }
+}
+
+async fn load_or_none<A>(repository: &EventSourcedRepository<A>, id: Uuid) -> Result<Option<A>, AppError>
+where
+    A: Aggregate,
+{
+    match repository.load(id).await {
+        Ok(aggregate) => Ok(Some(aggregate)),
+        Err(AppError::AggregateNotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Saves the first event of an aggregate; returns false if another instance starting at the
+/// same time created it first.
+async fn create<A>(repository: &EventSourcedRepository<A>, event: IdentityAccessEvent, metadata: &EventMetadata) -> Result<bool, AppError>
+where
+    A: Aggregate,
+{
+    match repository.save(&mut A::default(), &[event], metadata).await {
+        Ok(()) => Ok(true),
+        Err(AppError::ConcurrencyConflict) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+use crate::application::services::UserService;
+use crate::domain::identity_access::aggregates::user::UserStatus;
+use crate::infrastructure::persistence::effective_permissions::{EffectivePermission, EffectivePermissionStore};
+use crate::infrastructure::persistence::tenants::TenantStore;
+use crate::error::AppError;
+
+/// Why a policy decision allowed or denied access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecisionReason {
+    /// One of the user's roles is granted the permission, directly or through a menu above it.
+    Granted,
+    /// None of the user's roles is granted the permission.
+    NoMatchingGrant,
+    UserNotFound,
+    /// The user is deactivated or locked.
+    UserInactive,
+    /// The user belongs to a different tenant than the one asked about.
+    TenantMismatch,
+    /// The tenant is suspended.
+    TenantInactive,
+}
+
+impl DecisionReason {
+    /// The reason as reported to callers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DecisionReason::Granted => "granted",
+            DecisionReason::NoMatchingGrant => "no_matching_grant",
+            DecisionReason::UserNotFound => "user_not_found",
+            DecisionReason::UserInactive => "user_inactive",
+            DecisionReason::TenantMismatch => "tenant_mismatch",
+            DecisionReason::TenantInactive => "tenant_inactive",
+        }
+    }
+}
+
+/// The answer to "may this user use this permission in this tenant".
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessDecision {
+    pub reason: DecisionReason,
+    /// The grants through which the user holds the permission, direct grants first.
+    /// Only looked up once the user and tenant checks have passed.
+    pub grants: Vec<EffectivePermission>,
+}
+
+impl AccessDecision {
+    fn denied(reason: DecisionReason) -> Self {
+        Self { reason, grants: Vec::new() }
+    }
+
+    pub fn is_allowed(&self) -> bool {
+        self.reason == DecisionReason::Granted
+    }
+
+    /// The grant the decision is attributed to, preferring a direct grant over an inherited one.
+    pub fn matched_grant(&self) -> Option<&EffectivePermission> {
+        self.grants.first()
+    }
+}
+
+struct CachedDecision {
+    decided_at: Instant,
+    decision: AccessDecision,
+}
+
+/// Decides whether a user may use a permission in a tenant, for services that delegate
+/// authorization to IAM and call it on every request.
+///
+/// Decisions are cached for `cache_ttl`, so a change to a user, tenant or grant is reflected
+/// at most that long after it reaches the read models. The cache holds at most `cache_capacity`
+/// decisions; when it is full, expired decisions are dropped, and if none have expired it is
+/// cleared.
+pub struct PolicyDecisionService {
+    users: Arc<UserService>,
+    tenants: Arc<dyn TenantStore>,
+    permissions: Arc<dyn EffectivePermissionStore>,
+    cache_ttl: Duration,
+    cache_capacity: usize,
+    cache: RwLock<HashMap<(Uuid, Uuid, String), CachedDecision>>,
+}
+
+impl PolicyDecisionService {
+    pub fn new(
+        users: Arc<UserService>,
+        tenants: Arc<dyn TenantStore>,
+        permissions: Arc<dyn EffectivePermissionStore>,
+    ) -> Self {
+        Self {
+            users,
+            tenants,
+            permissions,
+            cache_ttl: Duration::from_secs(5),
+            cache_capacity: 10_000,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Caches decisions for `cache_ttl`; a zero TTL disables the cache.
+    pub fn with_cache(mut self, cache_ttl: Duration, cache_capacity: usize) -> Self {
+        self.cache_ttl = cache_ttl;
+        self.cache_capacity = cache_capacity;
+        self
+    }
+
+    pub async fn check(&self, user_id: Uuid, tenant_id: Uuid, code: &str) -> Result<AccessDecision, AppError> {
+        let key = (user_id, tenant_id, code.to_string());
+        if let Some(cached) = self.cache.read().await.get(&key)
+            && cached.decided_at.elapsed() < self.cache_ttl
+        {
+            return Ok(cached.decision.clone());
+        }
+
+        let decision = self.decide(user_id, tenant_id, code).await?;
+
+        if !self.cache_ttl.is_zero() && self.cache_capacity > 0 {
+            let mut cache = self.cache.write().await;
+            if cache.len() >= self.cache_capacity {
+                cache.retain(|_, cached| cached.decided_at.elapsed() < self.cache_ttl);
+                if cache.len() >= self.cache_capacity {
+                    cache.clear();
+                }
+            }
+            cache.insert(key, CachedDecision { decided_at: Instant::now(), decision: decision.clone() });
+        }
+
+        Ok(decision)
+    }
+
+    async fn decide(&self, user_id: Uuid, tenant_id: Uuid, code: &str) -> Result<AccessDecision, AppError> {
+        let user = match self.users.get_user(user_id).await {
+            Ok(user) => user,
+            Err(AppError::AggregateNotFound(_)) => return Ok(AccessDecision::denied(DecisionReason::UserNotFound)),
+            Err(e) => return Err(e),
+        };
+        if user.tenant_id() != tenant_id {
+            return Ok(AccessDecision::denied(DecisionReason::TenantMismatch));
+        }
+        if *user.status() == UserStatus::Inactive || user.is_locked_at(Utc::now()) {
+            return Ok(AccessDecision::denied(DecisionReason::UserInactive));
+        }
+        if let Some(tenant) = self.tenants.find_tenant(tenant_id).await?
+            && !tenant.is_active()
+        {
+            return Ok(AccessDecision::denied(DecisionReason::TenantInactive));
+        }
+
+        let mut grants = self.permissions.find_user_permission(user_id, code).await?;
+        if grants.is_empty() {
+            return Ok(AccessDecision::denied(DecisionReason::NoMatchingGrant));
+        }
+        grants.sort_by_key(|grant| (grant.permission_id != grant.granted_permission_id, grant.role_id, grant.granted_permission_id));
+
+        Ok(AccessDecision { reason: DecisionReason::Granted, grants })
+    }
+}
@@ -1,6 +1,11 @@
-use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, QuerySelect};
+use sea_orm::{Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, ColumnTrait, QuerySelect};
+use sea_orm::sea_query::Query;
 use uuid::Uuid;
 use crate::application::dtos as user_view;
+use crate::application::dtos::role_view;
+use crate::application::dtos::tenant_view;
+use crate::application::dtos::{organization_member_view, organization_view};
+use crate::application::dtos::{permission_view, role_permission_view};
 use crate::error::AppError;
 
 pub struct QueryService {
@@ -84,14 +89,293 @@ impl QueryService {
         let user = self.get_user_by_username(username, tenant_id).await?;
 
         if let Some(user) = user {
+            // 锁定期间不校验密码，避免暴露密码是否正确
+            if user.status == "locked" {
+                match user.locked_until {
+                    None => return Err(AppError::AccountLocked("Account is locked".to_string())),
+                    Some(until) if until > chrono::Utc::now() => {
+                        return Err(AppError::AccountLocked(format!("Account is locked until {}", until.to_rfc3339())));
+                    }
+                    // 锁定已过期，按正常用户处理
+                    Some(_) => {}
+                }
+            }
+
             let password_valid = bcrypt::verify(password, &user.password_hash)
                 .map_err(|e| AppError::InternalError(format!("Password verification failed: {}", e)))?;
 
-            if password_valid && user.status == "active" {
+            if password_valid && matches!(user.status.as_str(), "active" | "locked") {
                 return Ok(Some(user));
             }
         }
 
         Ok(None)
     }
+
+    /// 根据ID查询角色，已删除的角色同样返回
+    pub async fn get_role_by_id(&self, role_id: Uuid) -> Result<Option<role_view::Model>, AppError> {
+        let role = role_view::Entity::find_by_id(role_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(role)
+    }
+
+    /// 根据代码查询租户下未删除的角色
+    pub async fn get_role_by_code(&self, code: &str, tenant_id: Uuid) -> Result<Option<role_view::Model>, AppError> {
+        let role = role_view::Entity::find()
+            .filter(role_view::Column::Code.eq(code))
+            .filter(role_view::Column::TenantId.eq(tenant_id))
+            .filter(role_view::Column::Deleted.eq(false))
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(role)
+    }
+
+    /// 获取租户下的角色，默认不包含已删除的角色
+    pub async fn get_roles_by_tenant(&self, tenant_id: Uuid, include_deleted: bool, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<role_view::Model>, AppError> {
+        self.find_roles(tenant_id, None, include_deleted, limit, offset).await
+    }
+
+    /// 按名称或代码模糊搜索租户下未删除的角色
+    pub async fn search_roles(&self, keyword: &str, tenant_id: Uuid, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<role_view::Model>, AppError> {
+        self.find_roles(tenant_id, Some(keyword), false, limit, offset).await
+    }
+
+    async fn find_roles(&self, tenant_id: Uuid, keyword: Option<&str>, include_deleted: bool, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<role_view::Model>, AppError> {
+        let mut query = role_view::Entity::find()
+            .filter(role_view::Column::TenantId.eq(tenant_id))
+            .order_by_asc(role_view::Column::Code);
+
+        if !include_deleted {
+            query = query.filter(role_view::Column::Deleted.eq(false));
+        }
+
+        if let Some(keyword) = keyword {
+            query = query.filter(
+                Condition::any()
+                    .add(role_view::Column::Name.contains(keyword))
+                    .add(role_view::Column::Code.contains(keyword)),
+            );
+        }
+
+        if let Some(limit) = limit {
+            query = query.limit(limit);
+        }
+
+        if let Some(offset) = offset {
+            query = query.offset(offset);
+        }
+
+        let roles = query
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(roles)
+    }
+
+    /// 根据ID查询租户
+    pub async fn get_tenant_by_id(&self, tenant_id: Uuid) -> Result<Option<tenant_view::Model>, AppError> {
+        let tenant = tenant_view::Entity::find_by_id(tenant_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(tenant)
+    }
+
+    /// 获取租户列表，可按状态过滤
+    pub async fn get_tenants(&self, status: Option<&str>, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<tenant_view::Model>, AppError> {
+        let mut query = tenant_view::Entity::find().order_by_asc(tenant_view::Column::Code);
+
+        if let Some(status) = status {
+            query = query.filter(tenant_view::Column::Status.eq(status));
+        }
+
+        if let Some(limit) = limit {
+            query = query.limit(limit);
+        }
+
+        if let Some(offset) = offset {
+            query = query.offset(offset);
+        }
+
+        let tenants = query
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(tenants)
+    }
+
+    /// 根据ID查询租户下的组织
+    pub async fn get_organization_by_id(&self, organization_id: Uuid, tenant_id: Uuid) -> Result<Option<organization_view::Model>, AppError> {
+        let organization = organization_view::Entity::find_by_id(organization_id)
+            .filter(organization_view::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(organization)
+    }
+
+    /// 根据代码查询租户下的组织
+    pub async fn get_organization_by_code(&self, code: &str, tenant_id: Uuid) -> Result<Option<organization_view::Model>, AppError> {
+        let organization = organization_view::Entity::find()
+            .filter(organization_view::Column::Code.eq(code))
+            .filter(organization_view::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(organization)
+    }
+
+    /// 获取租户下的全部组织，按路径排序，父组织总在子组织之前
+    pub async fn get_organizations_by_tenant(&self, tenant_id: Uuid) -> Result<Vec<organization_view::Model>, AppError> {
+        let organizations = organization_view::Entity::find()
+            .filter(organization_view::Column::TenantId.eq(tenant_id))
+            .order_by_asc(organization_view::Column::Path)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(organizations)
+    }
+
+    /// 获取组织及其全部下级组织，按路径排序
+    pub async fn get_organization_subtree(&self, organization: &organization_view::Model) -> Result<Vec<organization_view::Model>, AppError> {
+        let organizations = organization_view::Entity::find()
+            .filter(organization_view::Column::TenantId.eq(organization.tenant_id))
+            .filter(organization_view::Column::Path.starts_with(&organization.path))
+            .order_by_asc(organization_view::Column::Path)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(organizations)
+    }
+
+    /// 组织是否还有下级组织
+    pub async fn has_child_organizations(&self, organization_id: Uuid) -> Result<bool, AppError> {
+        let child = organization_view::Entity::find()
+            .filter(organization_view::Column::ParentId.eq(organization_id))
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(child.is_some())
+    }
+
+    /// 获取属于任一给定组织的用户，按用户名排序
+    pub async fn get_organization_members(&self, organization_ids: Vec<Uuid>, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<user_view::Model>, AppError> {
+        let member_ids = Query::select()
+            .column(organization_member_view::Column::UserId)
+            .from(organization_member_view::Entity)
+            .and_where(organization_member_view::Column::OrganizationId.is_in(organization_ids))
+            .to_owned();
+
+        let mut query = user_view::Entity::find()
+            .filter(user_view::Column::Id.in_subquery(member_ids))
+            .order_by_asc(user_view::Column::Username);
+
+        if let Some(limit) = limit {
+            query = query.limit(limit);
+        }
+
+        if let Some(offset) = offset {
+            query = query.offset(offset);
+        }
+
+        let users = query
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(users)
+    }
+
+    /// 根据ID查询权限，已删除的权限同样返回
+    pub async fn get_permission_by_id(&self, permission_id: Uuid) -> Result<Option<permission_view::Model>, AppError> {
+        let permission = permission_view::Entity::find_by_id(permission_id)
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(permission)
+    }
+
+    /// 根据代码查询未删除的权限
+    pub async fn get_permission_by_code(&self, code: &str) -> Result<Option<permission_view::Model>, AppError> {
+        let permission = permission_view::Entity::find()
+            .filter(permission_view::Column::Code.eq(code))
+            .filter(permission_view::Column::Deleted.eq(false))
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(permission)
+    }
+
+    /// 获取权限目录，可按类型过滤，默认不包含已删除的权限
+    pub async fn get_permissions(&self, permission_type: Option<&str>, include_deleted: bool, limit: Option<u64>, offset: Option<u64>) -> Result<Vec<permission_view::Model>, AppError> {
+        let mut query = permission_view::Entity::find().order_by_asc(permission_view::Column::Code);
+
+        if let Some(permission_type) = permission_type {
+            query = query.filter(permission_view::Column::PermissionType.eq(permission_type));
+        }
+
+        if !include_deleted {
+            query = query.filter(permission_view::Column::Deleted.eq(false));
+        }
+
+        if let Some(limit) = limit {
+            query = query.limit(limit);
+        }
+
+        if let Some(offset) = offset {
+            query = query.offset(offset);
+        }
+
+        let permissions = query
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(permissions)
+    }
+
+    /// 权限是否还有未删除的下级权限
+    pub async fn has_child_permissions(&self, permission_id: Uuid) -> Result<bool, AppError> {
+        let child = permission_view::Entity::find()
+            .filter(permission_view::Column::ParentId.eq(permission_id))
+            .filter(permission_view::Column::Deleted.eq(false))
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(child.is_some())
+    }
+
+    /// 获取授予角色的权限，按代码排序
+    pub async fn get_role_permissions(&self, role_id: Uuid) -> Result<Vec<permission_view::Model>, AppError> {
+        let permission_ids = Query::select()
+            .column(role_permission_view::Column::PermissionId)
+            .from(role_permission_view::Entity)
+            .and_where(role_permission_view::Column::RoleId.eq(role_id))
+            .to_owned();
+
+        let permissions = permission_view::Entity::find()
+            .filter(permission_view::Column::Id.in_subquery(permission_ids))
+            .order_by_asc(permission_view::Column::Code)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Database error: {}", e)))?;
+
+        Ok(permissions)
+    }
 }
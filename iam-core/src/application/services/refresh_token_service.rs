@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use std::time::Duration;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use crate::application::services::user_service::UserService;
+use crate::domain::identity_access::aggregates::UserStatus;
+use crate::infrastructure::persistence::refresh_tokens::{RefreshToken, RefreshTokenStore};
+use crate::error::AppError;
+
+/// A refresh token handed to a client, together with who it was issued to.
+#[derive(Debug, Clone)]
+pub struct IssuedRefreshToken {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+    pub user_id: Uuid,
+    pub tenant_id: Uuid,
+    pub username: String,
+}
+
+/// Issues opaque refresh tokens and exchanges them for new ones.
+///
+/// Each token can be used once. Using a token that was already exchanged means it was
+/// copied, so its whole family is revoked and the legitimate client has to log in again.
+pub struct RefreshTokenService {
+    tokens: Arc<dyn RefreshTokenStore>,
+    users: Arc<UserService>,
+    ttl: Duration,
+}
+
+impl RefreshTokenService {
+    pub fn new(tokens: Arc<dyn RefreshTokenStore>, users: Arc<UserService>, ttl: Duration) -> Self {
+        Self { tokens, users, ttl }
+    }
+
+    /// Starts a new token family for a user who has just logged in.
+    pub async fn issue(&self, user_id: Uuid, tenant_id: Uuid, username: String) -> Result<IssuedRefreshToken, AppError> {
+        self.issue_in_family(Uuid::new_v4(), user_id, tenant_id, username).await
+    }
+
+    /// Exchanges `token` for a new token of the same family.
+    pub async fn rotate(&self, token: &str) -> Result<IssuedRefreshToken, AppError> {
+        let now = Utc::now();
+        let invalid = || AppError::AuthenticationError("Invalid refresh token".to_string());
+
+        let stored = self.tokens.find_token(&hash_token(token)).await?.ok_or_else(invalid)?;
+        if stored.revoked_at.is_some() || stored.expires_at <= now {
+            return Err(invalid());
+        }
+
+        // Losing the race against a concurrent use counts as reuse as well.
+        if stored.rotated_at.is_some() || !self.tokens.mark_rotated(&stored.token_hash, now).await? {
+            self.tokens.revoke_family(stored.family_id, now).await?;
+            tracing::warn!(
+                "Refresh token reuse detected for user {}, revoked token family {}",
+                stored.user_id,
+                stored.family_id
+            );
+            return Err(AppError::AuthenticationError("Refresh token has already been used".to_string()));
+        }
+
+        // Users locked or deactivated since they logged in lose their sessions.
+        let user = self.users.get_user(stored.user_id).await?;
+        if *user.status() == UserStatus::Inactive || user.is_locked_at(now) {
+            self.tokens.revoke_family(stored.family_id, now).await?;
+            return Err(AppError::AuthenticationError("User is not active".to_string()));
+        }
+
+        self.issue_in_family(stored.family_id, stored.user_id, stored.tenant_id, user.username().to_string()).await
+    }
+
+    /// Revokes the family of `token` on logout. Tokens that are unknown or belong to
+    /// another user are ignored, so logging out never reveals whether a token exists.
+    pub async fn revoke(&self, token: &str, user_id: Uuid) -> Result<(), AppError> {
+        if let Some(stored) = self.tokens.find_token(&hash_token(token)).await?
+            && stored.user_id == user_id
+        {
+            self.tokens.revoke_family(stored.family_id, Utc::now()).await?;
+        }
+        Ok(())
+    }
+
+    /// Revokes every refresh token issued to the user, ending all of their sessions.
+    pub async fn revoke_all(&self, user_id: Uuid) -> Result<(), AppError> {
+        self.tokens.revoke_user(user_id, Utc::now()).await
+    }
+
+    async fn issue_in_family(&self, family_id: Uuid, user_id: Uuid, tenant_id: Uuid, username: String) -> Result<IssuedRefreshToken, AppError> {
+        let now = Utc::now();
+        let expires_at = now + chrono::Duration::from_std(self.ttl)
+            .map_err(|e| AppError::InternalError(format!("Invalid refresh token lifetime: {}", e)))?;
+
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let token = URL_SAFE_NO_PAD.encode(bytes);
+
+        self.tokens.save_token(&RefreshToken {
+            token_hash: hash_token(&token),
+            family_id,
+            user_id,
+            tenant_id,
+            expires_at,
+            created_at: now,
+            rotated_at: None,
+            revoked_at: None,
+        }).await?;
+
+        Ok(IssuedRefreshToken { token, expires_at, user_id, tenant_id, username })
+    }
+}
+
+/// Refresh tokens are only stored as their SHA-256 hash, so a leaked table cannot be replayed.
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -1,25 +1,51 @@
 use std::sync::Arc;
 use uuid::Uuid;
+use crate::domain::identity_access::aggregates::permission::Permission;
 use crate::domain::identity_access::aggregates::role::Role;
+use crate::domain::identity_access::aggregates::user::User;
 use crate::domain::identity_access::commands::{
     CreateRoleCommand, UpdateRoleCommand, DeleteRoleCommand,
-    AssignUserRoleCommand, RemoveUserRoleCommand
+    AssignUserRoleCommand, RemoveUserRoleCommand,
+    GrantRolePermissionCommand, RevokeRolePermissionCommand
 };
-use crate::domain::identity_access::events::IdentityAccessEvent;
-use crate::infrastructure::persistence::event_store::EventStore;
+use crate::infrastructure::persistence::event_store::{EventMetadata, EventStore};
+use crate::infrastructure::persistence::repository::{EventSourcedRepository, RetryPolicy};
+use crate::infrastructure::persistence::snapshot_store::SnapshotPolicy;
 use crate::error::AppError;
 use anyhow::Result;
 
 pub struct RoleService {
-    event_store: Arc<dyn EventStore>,
+    roles: EventSourcedRepository<Role>,
+    users: EventSourcedRepository<User>,
+    permissions: EventSourcedRepository<Permission>,
 }
 
 impl RoleService {
     pub fn new(event_store: Arc<dyn EventStore>) -> Self {
-        Self { event_store }
+        Self {
+            roles: EventSourcedRepository::new(event_store.clone()),
+            users: EventSourcedRepository::new(event_store.clone()),
+            permissions: EventSourcedRepository::new(event_store),
+        }
     }
 
-    pub async fn create_role(&self, command: CreateRoleCommand) -> Result<Uuid, AppError> {
+    /// Restores roles, users and permissions from their latest snapshot and writes new snapshots according to `policy`.
+    pub fn with_snapshots(mut self, policy: SnapshotPolicy) -> Self {
+        self.roles = self.roles.with_snapshots(policy.clone());
+        self.users = self.users.with_snapshots(policy.clone());
+        self.permissions = self.permissions.with_snapshots(policy);
+        self
+    }
+
+    /// Re-runs commands that lose a concurrency race according to `policy`.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.roles = self.roles.with_retry(policy);
+        self.users = self.users.with_retry(policy);
+        self.permissions = self.permissions.with_retry(policy);
+        self
+    }
+
+    pub async fn create_role(&self, command: CreateRoleCommand, metadata: &EventMetadata) -> Result<Uuid, AppError> {
         let role_id = Uuid::new_v4();
 
         // 1. Execute business logic on the aggregate.
@@ -33,87 +59,88 @@ impl RoleService {
         .map_err(|e| AppError::DomainError(e.to_string()))?;
 
         // 2. Save the new event to the event store.
-        self.event_store.save_events(role_id, &[event], 0).await?;
+        self.roles.save(&mut Role::default(), &[event], metadata).await?;
 
         Ok(role_id)
     }
 
-    pub async fn update_role(&self, command: UpdateRoleCommand) -> Result<(), AppError> {
-        // 1. Load existing events to reconstruct the aggregate
-        let stored_events = self.event_store.load_events(command.role_id).await?;
-        let events: Vec<IdentityAccessEvent> = stored_events
-            .iter()
-            .map(|stored_event| serde_json::from_value(stored_event.payload.clone()))
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| AppError::SerializationError(e))?;
-        
-        let role = Role::from_events(&events);
+    pub async fn update_role(&self, command: UpdateRoleCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.roles.execute(command.role_id, metadata, |role| {
+            let event = role.update(command.name.clone(), command.description.clone())
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
 
-        // 2. Execute business logic on the aggregate
-        let event = role.update(command.name, command.description)
-            .map_err(|e| AppError::DomainError(e.to_string()))?;
+        Ok(())
+    }
 
-        // 3. Save the new event to the event store
-        self.event_store.save_events(command.role_id, &[event], role.version()).await?;
+    pub async fn delete_role(&self, command: DeleteRoleCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.roles.execute(command.role_id, metadata, |role| {
+            let event = role.delete()
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
 
         Ok(())
     }
 
-    pub async fn delete_role(&self, command: DeleteRoleCommand) -> Result<(), AppError> {
-        // 1. Load existing events to reconstruct the aggregate
-        let stored_events = self.event_store.load_events(command.role_id).await?;
-        let events: Vec<IdentityAccessEvent> = stored_events
-            .iter()
-            .map(|stored_event| serde_json::from_value(stored_event.payload.clone()))
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| AppError::SerializationError(e))?;
-        
-        let role = Role::from_events(&events);
+    pub async fn assign_user_role(&self, command: AssignUserRoleCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        // Role assignments live in the user's stream; the role is only loaded to check it can be assigned
+        let role = self.roles.load(command.role_id).await?;
+        if role.is_deleted() {
+            return Err(AppError::DomainError("Cannot assign a deleted role".to_string()));
+        }
 
-        // 2. Execute business logic on the aggregate
-        let event = role.delete()
-            .map_err(|e| AppError::DomainError(e.to_string()))?;
+        self.users.execute(command.user_id, metadata, |user| {
+            if role.tenant_id() != user.tenant_id() {
+                return Err(AppError::DomainError("Role belongs to a different tenant".to_string()));
+            }
 
-        // 3. Save the new event to the event store
-        self.event_store.save_events(command.role_id, &[event], role.version()).await?;
+            let event = user.assign_role(command.role_id)
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
 
         Ok(())
     }
 
-    pub async fn assign_user_role(&self, command: AssignUserRoleCommand) -> Result<(), AppError> {
-        let event = IdentityAccessEvent::UserRoleAssigned(crate::domain::identity_access::events::UserRoleAssigned {
-            user_id: command.user_id,
-            role_id: command.role_id,
-        });
-
-        // Save the event to the event store
-        // Note: In a real system, you might want to save this to a separate aggregate
-        // or use a different approach for user-role relationships
-        self.event_store.save_events(command.user_id, &[event], 0).await?;
+    pub async fn remove_user_role(&self, command: RemoveUserRoleCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.users.execute(command.user_id, metadata, |user| {
+            let event = user.remove_role(command.role_id)
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
 
         Ok(())
     }
 
-    pub async fn remove_user_role(&self, command: RemoveUserRoleCommand) -> Result<(), AppError> {
-        let event = IdentityAccessEvent::UserRoleRemoved(crate::domain::identity_access::events::UserRoleRemoved {
-            user_id: command.user_id,
-            role_id: command.role_id,
-        });
+    pub async fn grant_role_permission(&self, command: GrantRolePermissionCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        // Grants live in the role's stream; the permission is only loaded to check it can be granted
+        let permission = self.permissions.load(command.permission_id).await?;
+        if permission.is_deleted() {
+            return Err(AppError::DomainError("Cannot grant a deleted permission".to_string()));
+        }
+
+        self.roles.execute(command.role_id, metadata, |role| {
+            let event = role.grant_permission(command.permission_id)
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
 
-        // Save the event to the event store
-        self.event_store.save_events(command.user_id, &[event], 0).await?;
+    pub async fn revoke_role_permission(&self, command: RevokeRolePermissionCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.roles.execute(command.role_id, metadata, |role| {
+            let event = role.revoke_permission(command.permission_id)
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
 
         Ok(())
     }
 
     pub async fn get_role(&self, role_id: Uuid) -> Result<Role, AppError> {
-        let stored_events = self.event_store.load_events(role_id).await?;
-        let events: Vec<IdentityAccessEvent> = stored_events
-            .iter()
-            .map(|stored_event| serde_json::from_value(stored_event.payload.clone()))
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| AppError::SerializationError(e))?;
-        
-        Ok(Role::from_events(&events))
+        self.roles.load(role_id).await
     }
 }
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use crate::infrastructure::persistence::api_permissions::ApiPermissionStore;
+use crate::error::AppError;
+
+/// A route as `<METHOD> <path template>`, where a `:name` segment of the template matches
+/// any single path segment, for example `GET /api/v1/users/:id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteTemplate {
+    method: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Param(String),
+}
+
+impl RouteTemplate {
+    /// Parses a route, returning `None` if it is not `<METHOD> /path`.
+    pub fn parse(route: &str) -> Option<Self> {
+        let (method, path) = route.trim().split_once(' ')?;
+        if method.is_empty() || !path.starts_with('/') {
+            return None;
+        }
+
+        let segments = path_segments(path)
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => Segment::Param(name.to_string()),
+                None => Segment::Literal(segment.to_string()),
+            })
+            .collect();
+        Some(Self { method: method.to_ascii_uppercase(), segments })
+    }
+
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        if !self.method.eq_ignore_ascii_case(method) {
+            return false;
+        }
+
+        let mut segments = path_segments(path);
+        self.segments.iter().all(|expected| match (expected, segments.next()) {
+            (Segment::Literal(literal), Some(segment)) => literal == segment,
+            (Segment::Param(_), Some(segment)) => !segment.is_empty(),
+            (_, None) => false,
+        }) && segments.next().is_none()
+    }
+
+    /// The segment of a matching `path` that stands at the parameter `name` of the template.
+    pub fn param<'a>(&self, path: &'a str, name: &str) -> Option<&'a str> {
+        self.segments
+            .iter()
+            .zip(path_segments(path))
+            .find(|(segment, _)| matches!(segment, Segment::Param(param) if param == name))
+            .map(|(_, value)| value)
+    }
+
+    /// Orders templates matching the same path: the first literal segment where another
+    /// template has a parameter makes a template more specific, so `/users/me` beats `/users/:id`.
+    fn specificity(&self) -> Vec<bool> {
+        self.segments.iter().map(|segment| matches!(segment, Segment::Literal(_))).collect()
+    }
+}
+
+/// Splits a path into segments, ignoring a trailing slash.
+fn path_segments(path: &str) -> impl Iterator<Item = &str> {
+    let path = path.trim_start_matches('/').trim_end_matches('/');
+    path.split('/').filter(move |_| !path.is_empty())
+}
+
+/// What a request must satisfy according to the API permissions in the catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteRequirement {
+    /// The route is exempt from route-based authorization.
+    Exempt,
+    /// The caller must hold at least one of these permissions, whose routes match the request equally well.
+    Permissions(Vec<String>),
+    /// No API permission covers the route; the default policy decides.
+    Unmatched,
+}
+
+struct CachedRoutes {
+    loaded_at: Instant,
+    routes: Arc<Vec<(RouteTemplate, String)>>,
+}
+
+/// Resolves which API permissions protect a request from the routes registered in the permission catalog.
+///
+/// Routes are cached for `cache_ttl`, so a new or changed API permission is enforced at most
+/// that long after it reaches the read model.
+pub struct RoutePermissionService {
+    permissions: Arc<dyn ApiPermissionStore>,
+    exempt_routes: Vec<RouteTemplate>,
+    cache_ttl: Duration,
+    cache: RwLock<Option<CachedRoutes>>,
+}
+
+impl RoutePermissionService {
+    pub fn new(permissions: Arc<dyn ApiPermissionStore>) -> Self {
+        Self {
+            permissions,
+            exempt_routes: Vec::new(),
+            cache_ttl: Duration::from_secs(30),
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Skips authorization for requests matching any of `routes`; routes that cannot be parsed are ignored.
+    pub fn with_exempt_routes(mut self, routes: &[String]) -> Self {
+        self.exempt_routes = routes.iter().filter_map(|route| RouteTemplate::parse(route)).collect();
+        self
+    }
+
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    pub async fn resolve(&self, method: &str, path: &str) -> Result<RouteRequirement, AppError> {
+        if self.exempt_routes.iter().any(|route| route.matches(method, path)) {
+            return Ok(RouteRequirement::Exempt);
+        }
+
+        let routes = self.routes().await?;
+        let matching: Vec<&(RouteTemplate, String)> = routes
+            .iter()
+            .filter(|(route, _)| route.matches(method, path))
+            .collect();
+        let Some(best) = matching.iter().map(|(route, _)| route.specificity()).max() else {
+            return Ok(RouteRequirement::Unmatched);
+        };
+
+        let mut codes: Vec<String> = matching
+            .into_iter()
+            .filter(|(route, _)| route.specificity() == best)
+            .map(|(_, code)| code.clone())
+            .collect();
+        codes.sort();
+        codes.dedup();
+        Ok(RouteRequirement::Permissions(codes))
+    }
+
+    /// Returns the parsed routes, reloading them once the cached copy is older than `cache_ttl`.
+    async fn routes(&self) -> Result<Arc<Vec<(RouteTemplate, String)>>, AppError> {
+        if let Some(cached) = self.cache.read().await.as_ref()
+            && cached.loaded_at.elapsed() < self.cache_ttl
+        {
+            return Ok(cached.routes.clone());
+        }
+
+        let routes: Vec<(RouteTemplate, String)> = self
+            .permissions
+            .find_api_routes()
+            .await?
+            .into_iter()
+            .filter_map(|api_route| RouteTemplate::parse(&api_route.route).map(|route| (route, api_route.code)))
+            .collect();
+        let routes = Arc::new(routes);
+
+        *self.cache.write().await = Some(CachedRoutes { loaded_at: Instant::now(), routes: routes.clone() });
+        Ok(routes)
+    }
+}
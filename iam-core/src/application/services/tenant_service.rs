@@ -0,0 +1,102 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::domain::identity_access::aggregates::tenant::Tenant;
+use crate::domain::identity_access::commands::{
+    CreateTenantCommand, RenameTenantCommand, SuspendTenantCommand, ReactivateTenantCommand
+};
+use crate::infrastructure::persistence::event_store::{EventMetadata, EventStore};
+use crate::infrastructure::persistence::repository::{EventSourcedRepository, RetryPolicy};
+use crate::infrastructure::persistence::snapshot_store::SnapshotPolicy;
+use crate::error::AppError;
+use anyhow::Result;
+
+pub struct TenantService {
+    tenants: EventSourcedRepository<Tenant>,
+}
+
+impl TenantService {
+    pub fn new(event_store: Arc<dyn EventStore>) -> Self {
+        Self {
+            tenants: EventSourcedRepository::new(event_store),
+        }
+    }
+
+    /// Restores tenants from their latest snapshot and writes new snapshots according to `policy`.
+    pub fn with_snapshots(mut self, policy: SnapshotPolicy) -> Self {
+        self.tenants = self.tenants.with_snapshots(policy);
+        self
+    }
+
+    /// Re-runs commands that lose a concurrency race according to `policy`.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.tenants = self.tenants.with_retry(policy);
+        self
+    }
+
+    pub async fn create_tenant(&self, command: CreateTenantCommand, metadata: &EventMetadata) -> Result<Uuid, AppError> {
+        let tenant_id = Uuid::new_v4();
+
+        // 1. Execute business logic on the aggregate.
+        let event = Tenant::create(tenant_id, command.name, command.code)
+            .map_err(|e| AppError::DomainError(e.to_string()))?;
+
+        // 2. Save the new event to the event store.
+        self.tenants.save(&mut Tenant::default(), &[event], metadata).await?;
+
+        Ok(tenant_id)
+    }
+
+    /// Creates the tenant with the given id unless it already exists, for tenants such as the
+    /// platform tenant that configuration refers to by id. Returns whether it was created.
+    pub async fn ensure_tenant(&self, tenant_id: Uuid, command: CreateTenantCommand, metadata: &EventMetadata) -> Result<bool, AppError> {
+        match self.tenants.load(tenant_id).await {
+            Ok(_) => return Ok(false),
+            Err(AppError::AggregateNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        let event = Tenant::create(tenant_id, command.name, command.code)
+            .map_err(|e| AppError::DomainError(e.to_string()))?;
+
+        match self.tenants.save(&mut Tenant::default(), &[event], metadata).await {
+            Ok(()) => Ok(true),
+            // Another instance starting at the same time created it first.
+            Err(AppError::ConcurrencyConflict) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn rename_tenant(&self, command: RenameTenantCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.tenants.execute(command.tenant_id, metadata, |tenant| {
+            let event = tenant.rename(command.name.clone())
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
+
+    pub async fn suspend_tenant(&self, command: SuspendTenantCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.tenants.execute(command.tenant_id, metadata, |tenant| {
+            let event = tenant.suspend(command.reason.clone())
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
+
+    pub async fn reactivate_tenant(&self, command: ReactivateTenantCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.tenants.execute(command.tenant_id, metadata, |tenant| {
+            let event = tenant.reactivate(command.reason.clone())
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
+
+    pub async fn get_tenant(&self, tenant_id: Uuid) -> Result<Tenant, AppError> {
+        self.tenants.load(tenant_id).await
+    }
+}
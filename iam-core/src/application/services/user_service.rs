@@ -1,22 +1,39 @@
 use std::sync::Arc;
 use uuid::Uuid;
 use crate::domain::identity_access::aggregates::user::User;
-use crate::domain::identity_access::commands::{RegisterUserCommand, UpdateUserCommand, DeactivateUserCommand};
-use crate::domain::identity_access::events::IdentityAccessEvent;
-use crate::infrastructure::persistence::event_store::EventStore;
+use crate::domain::identity_access::commands::{
+    RegisterUserCommand, UpdateUserCommand, DeactivateUserCommand, ReactivateUserCommand, LockUserCommand, UnlockUserCommand,
+};
+use crate::infrastructure::persistence::event_store::{EventMetadata, EventStore};
+use crate::infrastructure::persistence::repository::{EventSourcedRepository, RetryPolicy};
+use crate::infrastructure::persistence::snapshot_store::SnapshotPolicy;
 use crate::error::AppError;
 use anyhow::Result;
 
 pub struct UserService {
-    event_store: Arc<dyn EventStore>,
+    users: EventSourcedRepository<User>,
 }
 
 impl UserService {
     pub fn new(event_store: Arc<dyn EventStore>) -> Self {
-        Self { event_store }
+        Self {
+            users: EventSourcedRepository::new(event_store),
+        }
     }
 
-    pub async fn register_user(&self, command: RegisterUserCommand) -> Result<Uuid, AppError> {
+    /// Restores users from their latest snapshot and writes new snapshots according to `policy`.
+    pub fn with_snapshots(mut self, policy: SnapshotPolicy) -> Self {
+        self.users = self.users.with_snapshots(policy);
+        self
+    }
+
+    /// Re-runs commands that lose a concurrency race according to `policy`.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.users = self.users.with_retry(policy);
+        self
+    }
+
+    pub async fn register_user(&self, command: RegisterUserCommand, metadata: &EventMetadata) -> Result<Uuid, AppError> {
         let user_id = Uuid::new_v4();
 
         // 1. Execute business logic on the aggregate.
@@ -33,61 +50,62 @@ impl UserService {
 
         // 2. Save the new event to the event store.
         // For a new aggregate, the expected version is 0.
-        self.event_store.save_events(user_id, &[event], 0).await?;
+        self.users.save(&mut User::default(), &[event], metadata).await?;
 
         Ok(user_id)
     }
 
-    pub async fn update_user(&self, command: UpdateUserCommand) -> Result<(), AppError> {
-        // 1. Load existing events to reconstruct the aggregate
-        let stored_events = self.event_store.load_events(command.user_id).await?;
-        let events: Vec<IdentityAccessEvent> = stored_events
-            .iter()
-            .map(|stored_event| serde_json::from_value(stored_event.payload.clone()))
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| AppError::SerializationError(e))?;
-        
-        let user = User::from_events(&events);
+    pub async fn update_user(&self, command: UpdateUserCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.users.execute(command.user_id, metadata, |user| {
+            let event = user.update(command.username.clone(), command.email.clone())
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
+
+        Ok(())
+    }
+
+    pub async fn deactivate_user(&self, command: DeactivateUserCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.users.execute(command.user_id, metadata, |user| {
+            let event = user.deactivate(command.reason.clone())
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
 
-        // 2. Execute business logic on the aggregate
-        let event = user.update(command.username, command.email)
-            .map_err(|e| AppError::DomainError(e.to_string()))?;
+        Ok(())
+    }
 
-        // 3. Save the new event to the event store
-        self.event_store.save_events(command.user_id, &[event], user.version()).await?;
+    pub async fn reactivate_user(&self, command: ReactivateUserCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.users.execute(command.user_id, metadata, |user| {
+            let event = user.reactivate(command.reason.clone())
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
 
         Ok(())
     }
 
-    pub async fn deactivate_user(&self, command: DeactivateUserCommand) -> Result<(), AppError> {
-        // 1. Load existing events to reconstruct the aggregate
-        let stored_events = self.event_store.load_events(command.user_id).await?;
-        let events: Vec<IdentityAccessEvent> = stored_events
-            .iter()
-            .map(|stored_event| serde_json::from_value(stored_event.payload.clone()))
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| AppError::SerializationError(e))?;
-        
-        let user = User::from_events(&events);
+    pub async fn lock_user(&self, command: LockUserCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.users.execute(command.user_id, metadata, |user| {
+            let event = user.lock(command.reason.clone(), command.locked_until, command.locked_by)
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
 
-        // 2. Execute business logic on the aggregate
-        let event = user.deactivate(command.reason)
-            .map_err(|e| AppError::DomainError(e.to_string()))?;
+        Ok(())
+    }
 
-        // 3. Save the new event to the event store
-        self.event_store.save_events(command.user_id, &[event], user.version()).await?;
+    pub async fn unlock_user(&self, command: UnlockUserCommand, metadata: &EventMetadata) -> Result<(), AppError> {
+        self.users.execute(command.user_id, metadata, |user| {
+            let event = user.unlock(command.reason.clone(), command.unlocked_by)
+                .map_err(|e| AppError::DomainError(e.to_string()))?;
+            Ok(vec![event])
+        }).await?;
 
         Ok(())
     }
 
     pub async fn get_user(&self, user_id: Uuid) -> Result<User, AppError> {
-        let stored_events = self.event_store.load_events(user_id).await?;
-        let events: Vec<IdentityAccessEvent> = stored_events
-            .iter()
-            .map(|stored_event| serde_json::from_value(stored_event.payload.clone()))
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| AppError::SerializationError(e))?;
-        
-        Ok(User::from_events(&events))
+        self.users.load(user_id).await
     }
 }
@@ -1,5 +1,18 @@
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::net::IpAddr;
+use std::str::FromStr;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// 默认不做路由权限检查的接口
+const DEFAULT_EXEMPT_ROUTES: [&str; 5] = [
+    "POST /api/v1/auth/login",
+    "POST /api/v1/auth/refresh",
+    "POST /api/v1/auth/logout",
+    "POST /api/v1/auth/logout-all",
+    "POST /api/v1/users",
+];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseConfig {
@@ -13,12 +26,140 @@ pub struct ServerConfig {
     pub host: String,
     pub port: u16,
     pub cors_origins: Vec<String>,
+    /// 可信反向代理的IP地址，只有来自这些地址的连接才采用 X-Forwarded-For 和 X-Real-IP 头部中的客户端IP
+    pub trusted_proxies: Vec<IpAddr>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JwtConfig {
     pub secret: String,
     pub expiration_hours: u64,
+    /// 刷新令牌有效小时数，每次刷新后重新计算
+    pub refresh_expiration_hours: u64,
+    /// 签名私钥目录，目录中每个 <kid>.pem 或 <kid>@<生效时间>.pem 文件是一个 RS256 或 ES256 密钥，生效时间为 UTC，
+    /// 例如 20261017T090000Z，应晚于验证方缓存 JWKS 的时间；未配置时使用 secret 进行 HS256 签名
+    pub keys_dir: Option<String>,
+    /// 固定使用的签名密钥ID，未配置时使用最新生效的密钥
+    pub signing_key_id: Option<String>,
+    /// 重新加载密钥目录的间隔秒数
+    pub key_reload_interval_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotConfig {
+    /// 每隔多少个事件生成一次聚合快照，0 表示禁用快照
+    pub frequency: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryConfig {
+    /// 命令遇到并发冲突时的最大尝试次数（含首次），1 表示不重试
+    pub max_attempts: u32,
+    /// 首次重试前的等待毫秒数，之后每次翻倍
+    pub backoff_ms: u64,
+    /// 两次尝试之间的最大等待毫秒数
+    pub max_backoff_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectionConfig {
+    /// 每次从事件存储读取的事件数量
+    pub batch_size: u64,
+    /// 所有投影追上后再次轮询前的等待毫秒数
+    pub poll_interval_ms: u64,
+    /// 等待缺失事件位置提交的最长毫秒数，超时后跳过
+    pub gap_timeout_ms: u64,
+    /// 跳过的事件位置继续查找的毫秒数，期间提交的事件仍会投影，超时后放弃并记录错误
+    pub skipped_retention_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformConfig {
+    /// 平台租户ID，该租户下拥有 platform:admin 权限的用户是平台管理员；未配置时管理接口全部拒绝
+    pub tenant_id: Option<Uuid>,
+    /// 平台租户不存在时，启动时以此名称创建
+    pub tenant_name: String,
+    /// 平台租户不存在时，启动时以此代码创建
+    pub tenant_code: String,
+    /// 第一个平台管理员，配置后启动时确保该账户存在并拥有平台管理员角色
+    pub admin: Option<PlatformAdminConfig>,
+}
+
+/// 平台管理员账户的初始凭据，账户已存在时不会修改其密码
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PlatformAdminConfig {
+    pub username: String,
+    pub email: String,
+    pub password: String,
+}
+
+impl std::fmt::Debug for PlatformAdminConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PlatformAdminConfig")
+            .field("username", &self.username)
+            .field("email", &self.email)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantConfig {
+    /// 租户子域名的基础域名，例如 iam.example.com 时 acme.iam.example.com 解析为租户 acme；未配置时不按子域名解析
+    pub base_domain: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginProtectionConfig {
+    /// 统计窗口内同一用户登录失败多少次后锁定账户
+    pub max_failed_attempts_per_user: u32,
+    /// 统计窗口内同一客户端IP登录失败多少次后拒绝其登录
+    pub max_failed_attempts_per_ip: u32,
+    /// 登录失败的统计窗口秒数
+    pub attempt_window_secs: u64,
+    /// 首次锁定的秒数，之后每次连续锁定翻倍
+    pub lockout_base_secs: u64,
+    /// 单次锁定的最大秒数
+    pub lockout_max_secs: u64,
+}
+
+/// 没有任何 API 权限匹配的路由的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnmatchedRoutePolicy {
+    Allow,
+    Deny,
+}
+
+impl FromStr for UnmatchedRoutePolicy {
+    type Err = ConfigError;
+
+    /// 不区分大小写；无法识别的取值报错，而不是悄悄退回某个策略
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "allow" => Ok(UnmatchedRoutePolicy::Allow),
+            "deny" => Ok(UnmatchedRoutePolicy::Deny),
+            _ => Err(ConfigError::InvalidValue {
+                name: "AUTHZ_UNMATCHED_ROUTE_POLICY",
+                value: value.to_string(),
+                expected: "allow or deny",
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizationConfig {
+    /// 请求没有匹配的 API 权限时放行（allow）还是拒绝（deny），默认拒绝
+    pub unmatched_route_policy: UnmatchedRoutePolicy,
+    /// 不做路由权限检查的接口，格式与 API 权限的路由相同，例如 "POST /api/v1/auth/login"
+    pub exempt_routes: Vec<String>,
+    /// API 权限路由的缓存秒数，新增或修改的 API 权限最多延迟这么久生效
+    pub route_cache_secs: u64,
+    /// 授权检查接口的决策缓存秒数，为 0 时不缓存
+    pub decision_cache_secs: u64,
+    /// 最多缓存的授权决策数
+    pub decision_cache_capacity: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,11 +167,39 @@ pub struct AppConfig {
     pub database: DatabaseConfig,
     pub server: ServerConfig,
     pub jwt: JwtConfig,
+    pub snapshot: SnapshotConfig,
+    pub retry: RetryConfig,
+    pub projection: ProjectionConfig,
+    pub platform: PlatformConfig,
+    pub tenant: TenantConfig,
+    pub login: LoginProtectionConfig,
+    pub authorization: AuthorizationConfig,
     pub environment: String,
 }
 
+/// 配置加载错误
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Environment variable error: {0}")]
+    Var(#[from] env::VarError),
+
+    #[error("Invalid value {value:?} for {name}, expected {expected}")]
+    InvalidValue {
+        name: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+}
+
 impl AppConfig {
-    pub fn from_env() -> Result<Self, env::VarError> {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        // 使用密钥目录时不再需要共享密钥
+        let jwt_keys_dir = env::var("JWT_KEYS_DIR").ok().filter(|dir| !dir.trim().is_empty());
+        let jwt_secret = match env::var("JWT_SECRET") {
+            Err(_) if jwt_keys_dir.is_some() => String::new(),
+            secret => secret?,
+        };
+
         Ok(AppConfig {
             database: DatabaseConfig {
                 url: env::var("DATABASE_URL")?,
@@ -54,13 +223,135 @@ impl AppConfig {
                     .split(',')
                     .map(|s| s.trim().to_string())
                     .collect(),
+                trusted_proxies: env::var("TRUSTED_PROXIES")
+                    .unwrap_or_default()
+                    .split(',')
+                    .filter_map(|ip| ip.trim().parse().ok())
+                    .collect(),
             },
             jwt: JwtConfig {
-                secret: env::var("JWT_SECRET")?,
+                secret: jwt_secret,
                 expiration_hours: env::var("JWT_EXPIRATION_HOURS")
                     .unwrap_or_else(|_| "24".to_string())
                     .parse()
                     .unwrap_or(24),
+                refresh_expiration_hours: env::var("JWT_REFRESH_EXPIRATION_HOURS")
+                    .unwrap_or_else(|_| "720".to_string())
+                    .parse()
+                    .unwrap_or(720),
+                keys_dir: jwt_keys_dir,
+                signing_key_id: env::var("JWT_SIGNING_KEY_ID").ok().filter(|kid| !kid.trim().is_empty()),
+                key_reload_interval_secs: env::var("JWT_KEY_RELOAD_INTERVAL_SECS")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse()
+                    .unwrap_or(60),
+            },
+            snapshot: SnapshotConfig {
+                frequency: env::var("SNAPSHOT_FREQUENCY")
+                    .unwrap_or_else(|_| "50".to_string())
+                    .parse()
+                    .unwrap_or(50),
+            },
+            retry: RetryConfig {
+                max_attempts: env::var("COMMAND_RETRY_MAX_ATTEMPTS")
+                    .unwrap_or_else(|_| "3".to_string())
+                    .parse()
+                    .unwrap_or(3),
+                backoff_ms: env::var("COMMAND_RETRY_BACKOFF_MS")
+                    .unwrap_or_else(|_| "20".to_string())
+                    .parse()
+                    .unwrap_or(20),
+                max_backoff_ms: env::var("COMMAND_RETRY_MAX_BACKOFF_MS")
+                    .unwrap_or_else(|_| "200".to_string())
+                    .parse()
+                    .unwrap_or(200),
+            },
+            projection: ProjectionConfig {
+                batch_size: env::var("PROJECTION_BATCH_SIZE")
+                    .unwrap_or_else(|_| "100".to_string())
+                    .parse()
+                    .unwrap_or(100),
+                poll_interval_ms: env::var("PROJECTION_POLL_INTERVAL_MS")
+                    .unwrap_or_else(|_| "500".to_string())
+                    .parse()
+                    .unwrap_or(500),
+                gap_timeout_ms: env::var("PROJECTION_GAP_TIMEOUT_MS")
+                    .unwrap_or_else(|_| "5000".to_string())
+                    .parse()
+                    .unwrap_or(5000),
+                skipped_retention_ms: env::var("PROJECTION_SKIPPED_RETENTION_MS")
+                    .unwrap_or_else(|_| "600000".to_string())
+                    .parse()
+                    .unwrap_or(600000),
+            },
+            platform: PlatformConfig {
+                tenant_id: env::var("PLATFORM_TENANT_ID")
+                    .ok()
+                    .and_then(|id| Uuid::parse_str(id.trim()).ok()),
+                tenant_name: env::var("PLATFORM_TENANT_NAME").unwrap_or_else(|_| "Platform".to_string()),
+                tenant_code: env::var("PLATFORM_TENANT_CODE").unwrap_or_else(|_| "platform".to_string()),
+                admin: match env::var("PLATFORM_ADMIN_USERNAME").ok().filter(|username| !username.trim().is_empty()) {
+                    Some(username) => Some(PlatformAdminConfig {
+                        username: username.trim().to_string(),
+                        email: env::var("PLATFORM_ADMIN_EMAIL")?,
+                        password: env::var("PLATFORM_ADMIN_PASSWORD")?,
+                    }),
+                    None => None,
+                },
+            },
+            tenant: TenantConfig {
+                base_domain: env::var("TENANT_BASE_DOMAIN")
+                    .ok()
+                    .map(|domain| domain.trim().trim_start_matches('.').to_lowercase())
+                    .filter(|domain| !domain.is_empty()),
+            },
+            login: LoginProtectionConfig {
+                max_failed_attempts_per_user: env::var("LOGIN_MAX_FAILED_ATTEMPTS_PER_USER")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()
+                    .unwrap_or(5),
+                max_failed_attempts_per_ip: env::var("LOGIN_MAX_FAILED_ATTEMPTS_PER_IP")
+                    .unwrap_or_else(|_| "20".to_string())
+                    .parse()
+                    .unwrap_or(20),
+                attempt_window_secs: env::var("LOGIN_ATTEMPT_WINDOW_SECS")
+                    .unwrap_or_else(|_| "900".to_string())
+                    .parse()
+                    .unwrap_or(900),
+                lockout_base_secs: env::var("LOGIN_LOCKOUT_BASE_SECS")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse()
+                    .unwrap_or(60),
+                lockout_max_secs: env::var("LOGIN_LOCKOUT_MAX_SECS")
+                    .unwrap_or_else(|_| "3600".to_string())
+                    .parse()
+                    .unwrap_or(3600),
+            },
+            authorization: AuthorizationConfig {
+                // 未配置时拒绝，新增的接口在配置 API 权限之前不会对外开放
+                unmatched_route_policy: match env::var("AUTHZ_UNMATCHED_ROUTE_POLICY") {
+                    Err(env::VarError::NotPresent) => UnmatchedRoutePolicy::Deny,
+                    value => value?.parse()?,
+                },
+                // 默认豁免登录、注册等认证前或只需认证的接口
+                exempt_routes: env::var("AUTHZ_EXEMPT_ROUTES")
+                    .unwrap_or_else(|_| DEFAULT_EXEMPT_ROUTES.join(","))
+                    .split(',')
+                    .map(|route| route.trim().to_string())
+                    .filter(|route| !route.is_empty())
+                    .collect(),
+                route_cache_secs: env::var("AUTHZ_ROUTE_CACHE_SECS")
+                    .unwrap_or_else(|_| "30".to_string())
+                    .parse()
+                    .unwrap_or(30),
+                decision_cache_secs: env::var("AUTHZ_DECISION_CACHE_SECS")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()
+                    .unwrap_or(5),
+                decision_cache_capacity: env::var("AUTHZ_DECISION_CACHE_CAPACITY")
+                    .unwrap_or_else(|_| "10000".to_string())
+                    .parse()
+                    .unwrap_or(10000),
             },
             environment: env::var("ENVIRONMENT").unwrap_or_else(|_| "development".to_string()),
         })
@@ -0,0 +1,29 @@
+use serde::{de::DeserializeOwned, Serialize};
+use uuid::Uuid;
+use crate::domain::identity_access::events::IdentityAccessEvent;
+
+/// Behaviour shared by all event-sourced aggregates.
+///
+/// An aggregate starts from `Default`, is rebuilt by applying its events in order,
+/// and can be serialized so that its state can be stored as a snapshot.
+pub trait Aggregate: Default + Serialize + DeserializeOwned + Send + Sync {
+    /// Name of the aggregate type, used for snapshots and error messages.
+    const AGGREGATE_TYPE: &'static str;
+
+    fn id(&self) -> Uuid;
+
+    /// The number of events applied so far, i.e. the sequence of the last one.
+    fn version(&self) -> u64;
+
+    /// Applies an event to the aggregate to change its state.
+    fn apply(&mut self, event: &IdentityAccessEvent);
+
+    /// Reconstructs the aggregate state from a series of events.
+    fn from_events(events: &[IdentityAccessEvent]) -> Self {
+        let mut aggregate = Self::default();
+        for event in events {
+            aggregate.apply(event);
+        }
+        aggregate
+    }
+}
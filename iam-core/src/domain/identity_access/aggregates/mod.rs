@@ -1,4 +1,13 @@
+pub mod aggregate;
 pub mod user;
 pub mod role;
+pub mod tenant;
+pub mod organization;
+pub mod permission;
 
-pub use user::*;
\ No newline at end of file
+pub use aggregate::*;
+pub use user::*;
+pub use role::*;
+pub use tenant::*;
+pub use organization::*;
+pub use permission::*;
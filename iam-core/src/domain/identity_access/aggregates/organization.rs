@@ -0,0 +1,173 @@
+use uuid::Uuid;
+use crate::domain::identity_access::events::{
+    IdentityAccessEvent, OrganizationCreated, OrganizationRenamed, OrganizationMoved, OrganizationDeleted
+};
+use crate::domain::identity_access::aggregates::aggregate::Aggregate;
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+/// The state of the Organization aggregate, a node in a tenant's department tree.
+/// It is serializable so that it can be stored as a snapshot.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Organization {
+    id: Uuid,
+    tenant_id: Uuid,
+    parent_id: Option<Uuid>,
+    name: String,
+    code: String,
+    #[serde(default)]
+    deleted: bool,
+    version: u64,
+}
+
+impl Organization {
+    /// Business logic for creating a new organization, either as a root or under `parent`.
+    pub fn create(
+        id: Uuid,
+        tenant_id: Uuid,
+        parent: Option<&Organization>,
+        name: String,
+        code: String,
+    ) -> Result<IdentityAccessEvent> {
+        if name.is_empty() {
+            return Err(anyhow!("Organization name cannot be empty"));
+        }
+        if code.is_empty() {
+            return Err(anyhow!("Organization code cannot be empty"));
+        }
+        if !code.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+            return Err(anyhow!("Organization code can only contain alphanumeric characters, underscores and hyphens"));
+        }
+        if let Some(parent) = parent {
+            Self::check_parent(tenant_id, parent)?;
+        }
+
+        Ok(IdentityAccessEvent::OrganizationCreated(OrganizationCreated {
+            organization_id: id,
+            tenant_id,
+            parent_id: parent.map(|parent| parent.id),
+            name,
+            code,
+        }))
+    }
+
+    /// Business logic for renaming an organization.
+    pub fn rename(&self, name: String) -> Result<IdentityAccessEvent> {
+        if self.deleted {
+            return Err(anyhow!("Cannot rename a deleted organization"));
+        }
+        if name.is_empty() {
+            return Err(anyhow!("Organization name cannot be empty"));
+        }
+
+        Ok(IdentityAccessEvent::OrganizationRenamed(OrganizationRenamed {
+            organization_id: self.id,
+            name,
+        }))
+    }
+
+    /// Business logic for moving the organization, with its whole subtree, under a new parent.
+    ///
+    /// `parent_ancestors` are the ids of every organization above `parent`; the move is
+    /// rejected when this organization is among them, as it would then become its own ancestor.
+    pub fn move_to(&self, parent: Option<&Organization>, parent_ancestors: &[Uuid]) -> Result<IdentityAccessEvent> {
+        if self.deleted {
+            return Err(anyhow!("Cannot move a deleted organization"));
+        }
+        if let Some(parent) = parent {
+            Self::check_parent(self.tenant_id, parent)?;
+            if parent.id == self.id || parent_ancestors.contains(&self.id) {
+                return Err(anyhow!("Cannot move an organization under itself or one of its descendants"));
+            }
+        }
+        let parent_id = parent.map(|parent| parent.id);
+        if parent_id == self.parent_id {
+            return Err(anyhow!("Organization is already under this parent"));
+        }
+
+        Ok(IdentityAccessEvent::OrganizationMoved(OrganizationMoved {
+            organization_id: self.id,
+            previous_parent_id: self.parent_id,
+            parent_id,
+        }))
+    }
+
+    /// Business logic for deleting an organization.
+    pub fn delete(&self) -> Result<IdentityAccessEvent> {
+        if self.deleted {
+            return Err(anyhow!("Organization is already deleted"));
+        }
+
+        Ok(IdentityAccessEvent::OrganizationDeleted(OrganizationDeleted {
+            organization_id: self.id,
+        }))
+    }
+
+    fn check_parent(tenant_id: Uuid, parent: &Organization) -> Result<()> {
+        if parent.tenant_id != tenant_id {
+            return Err(anyhow!("Parent organization belongs to a different tenant"));
+        }
+        if parent.deleted {
+            return Err(anyhow!("Parent organization is deleted"));
+        }
+        Ok(())
+    }
+
+    // Getters
+    pub fn tenant_id(&self) -> Uuid {
+        self.tenant_id
+    }
+
+    pub fn parent_id(&self) -> Option<Uuid> {
+        self.parent_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+}
+
+impl Aggregate for Organization {
+    const AGGREGATE_TYPE: &'static str = "Organization";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn apply(&mut self, event: &IdentityAccessEvent) {
+        match event {
+            IdentityAccessEvent::OrganizationCreated(e) => {
+                self.id = e.organization_id;
+                self.tenant_id = e.tenant_id;
+                self.parent_id = e.parent_id;
+                self.name = e.name.clone();
+                self.code = e.code.clone();
+            }
+            IdentityAccessEvent::OrganizationRenamed(e) => {
+                self.name = e.name.clone();
+            }
+            IdentityAccessEvent::OrganizationMoved(e) => {
+                self.parent_id = e.parent_id;
+            }
+            IdentityAccessEvent::OrganizationDeleted(_) => {
+                self.deleted = true;
+            }
+            _ => {
+                // Other events don't affect organization state
+            }
+        }
+        self.version += 1;
+    }
+}
@@ -0,0 +1,236 @@
+use uuid::Uuid;
+use crate::domain::identity_access::events::{
+    IdentityAccessEvent, PermissionCreated, PermissionUpdated, PermissionDeleted
+};
+use crate::domain::identity_access::aggregates::aggregate::Aggregate;
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+/// HTTP methods an API permission's route can name.
+const API_METHODS: [&str; 5] = ["GET", "POST", "PUT", "PATCH", "DELETE"];
+
+/// The state of the Permission aggregate, an entry in the platform-wide permission catalog.
+/// It is serializable so that it can be stored as a snapshot.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Permission {
+    id: Uuid,
+    parent_id: Option<Uuid>,
+    permission_type: PermissionType,
+    name: String,
+    code: String,
+    route: Option<String>,
+    icon: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    deleted: bool,
+    version: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum PermissionType {
+    #[default]
+    Menu,
+    Button,
+    Api,
+}
+
+impl PermissionType {
+    /// The type as recorded in events and read models.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PermissionType::Menu => "menu",
+            PermissionType::Button => "button",
+            PermissionType::Api => "api",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "menu" => Ok(PermissionType::Menu),
+            "button" => Ok(PermissionType::Button),
+            "api" => Ok(PermissionType::Api),
+            other => Err(anyhow!("Unknown permission type '{}', expected menu, button or api", other)),
+        }
+    }
+
+    /// Checks a route against the type: menus may link to a page, buttons have no route and
+    /// API permissions must name the endpoint they protect as `<METHOD> <path template>`.
+    fn check_route(&self, route: Option<&str>) -> Result<()> {
+        match (self, route) {
+            (PermissionType::Menu, Some(route)) if !route.starts_with('/') => {
+                Err(anyhow!("Menu routes must start with '/'"))
+            }
+            (PermissionType::Button, Some(_)) => Err(anyhow!("Button permissions cannot have a route")),
+            (PermissionType::Api, None) => Err(anyhow!("API permissions require a route")),
+            (PermissionType::Api, Some(route)) => match route.split_once(' ') {
+                Some((method, path)) if API_METHODS.contains(&method) && path.starts_with('/') && !path.contains(' ') => {
+                    Ok(())
+                }
+                _ => Err(anyhow!("API routes must have the form '<METHOD> /path', for example 'GET /api/v1/users/:id'")),
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Permission {
+    /// Business logic for creating a new permission, either at the top of the catalog or under `parent`.
+    /// Only menus can contain other permissions, and buttons always belong to a menu.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        id: Uuid,
+        parent: Option<&Permission>,
+        permission_type: &str,
+        name: String,
+        code: String,
+        route: Option<String>,
+        icon: Option<String>,
+        description: Option<String>,
+    ) -> Result<IdentityAccessEvent> {
+        let permission_type = PermissionType::parse(permission_type)?;
+        if name.is_empty() {
+            return Err(anyhow!("Permission name cannot be empty"));
+        }
+        if code.is_empty() {
+            return Err(anyhow!("Permission code cannot be empty"));
+        }
+        if !code.chars().all(|c| c.is_alphanumeric() || matches!(c, ':' | '_' | '-' | '.')) {
+            return Err(anyhow!("Permission code can only contain alphanumeric characters, ':', '_', '-' and '.'"));
+        }
+        permission_type.check_route(route.as_deref())?;
+
+        match parent {
+            Some(parent) if parent.deleted => return Err(anyhow!("Parent permission is deleted")),
+            Some(parent) if parent.permission_type != PermissionType::Menu => {
+                return Err(anyhow!("Only menu permissions can contain other permissions"));
+            }
+            None if permission_type == PermissionType::Button => {
+                return Err(anyhow!("Button permissions must belong to a menu"));
+            }
+            _ => {}
+        }
+
+        Ok(IdentityAccessEvent::PermissionCreated(PermissionCreated {
+            permission_id: id,
+            parent_id: parent.map(|parent| parent.id),
+            permission_type: permission_type.as_str().to_string(),
+            name,
+            code,
+            route,
+            icon,
+            description,
+        }))
+    }
+
+    /// Business logic for updating a permission; fields left as `None` keep their value.
+    pub fn update(
+        &self,
+        name: Option<String>,
+        route: Option<String>,
+        icon: Option<String>,
+        description: Option<String>,
+    ) -> Result<IdentityAccessEvent> {
+        if self.deleted {
+            return Err(anyhow!("Cannot update a deleted permission"));
+        }
+        if name.as_ref().is_some_and(|name| name.is_empty()) {
+            return Err(anyhow!("Permission name cannot be empty"));
+        }
+        if route.is_some() {
+            self.permission_type.check_route(route.as_deref())?;
+        }
+
+        Ok(IdentityAccessEvent::PermissionUpdated(PermissionUpdated {
+            permission_id: self.id,
+            name,
+            route,
+            icon,
+            description,
+        }))
+    }
+
+    /// Business logic for deleting a permission.
+    pub fn delete(&self) -> Result<IdentityAccessEvent> {
+        if self.deleted {
+            return Err(anyhow!("Permission is already deleted"));
+        }
+
+        Ok(IdentityAccessEvent::PermissionDeleted(PermissionDeleted {
+            permission_id: self.id,
+        }))
+    }
+
+    // Getters
+    pub fn parent_id(&self) -> Option<Uuid> {
+        self.parent_id
+    }
+
+    pub fn permission_type(&self) -> PermissionType {
+        self.permission_type
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn route(&self) -> Option<&str> {
+        self.route.as_deref()
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+}
+
+impl Aggregate for Permission {
+    const AGGREGATE_TYPE: &'static str = "Permission";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn apply(&mut self, event: &IdentityAccessEvent) {
+        match event {
+            IdentityAccessEvent::PermissionCreated(e) => {
+                self.id = e.permission_id;
+                self.parent_id = e.parent_id;
+                // Only validated types are ever recorded
+                self.permission_type = PermissionType::parse(&e.permission_type).unwrap_or_default();
+                self.name = e.name.clone();
+                self.code = e.code.clone();
+                self.route = e.route.clone();
+                self.icon = e.icon.clone();
+                self.description = e.description.clone();
+            }
+            IdentityAccessEvent::PermissionUpdated(e) => {
+                if let Some(ref name) = e.name {
+                    self.name = name.clone();
+                }
+                if let Some(ref route) = e.route {
+                    self.route = Some(route.clone());
+                }
+                if let Some(ref icon) = e.icon {
+                    self.icon = Some(icon.clone());
+                }
+                if let Some(ref description) = e.description {
+                    self.description = Some(description.clone());
+                }
+            }
+            IdentityAccessEvent::PermissionDeleted(_) => {
+                self.deleted = true;
+            }
+            _ => {
+                // Other events don't affect permission state
+            }
+        }
+        self.version += 1;
+    }
+}
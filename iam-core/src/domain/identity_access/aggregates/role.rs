@@ -1,17 +1,25 @@
 use uuid::Uuid;
+use std::collections::BTreeSet;
 use crate::domain::identity_access::events::{
-    IdentityAccessEvent, RoleCreated, RoleUpdated, RoleDeleted
+    IdentityAccessEvent, RoleCreated, RoleUpdated, RoleDeleted, RolePermissionGranted, RolePermissionRevoked
 };
+use crate::domain::identity_access::aggregates::aggregate::Aggregate;
 use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
 
 /// The state of the Role aggregate.
-#[derive(Debug, Default)]
+/// It is serializable so that it can be stored as a snapshot.
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Role {
     id: Uuid,
     tenant_id: Uuid,
     name: String,
     code: String,
     description: Option<String>,
+    #[serde(default)]
+    deleted: bool,
+    #[serde(default)]
+    permissions: BTreeSet<Uuid>,
     version: u64,
 }
 
@@ -48,6 +56,9 @@ impl Role {
 
     /// Business logic for updating a role.
     pub fn update(&self, name: Option<String>, description: Option<String>) -> Result<IdentityAccessEvent> {
+        if self.deleted {
+            return Err(anyhow!("Cannot update a deleted role"));
+        }
         if let Some(ref name) = name {
             if name.is_empty() {
                 return Err(anyhow!("Role name cannot be empty"));
@@ -63,54 +74,43 @@ impl Role {
 
     /// Business logic for deleting a role.
     pub fn delete(&self) -> Result<IdentityAccessEvent> {
+        if self.deleted {
+            return Err(anyhow!("Role is already deleted"));
+        }
+
         Ok(IdentityAccessEvent::RoleDeleted(RoleDeleted {
             role_id: self.id,
         }))
     }
 
-    /// Applies an event to the aggregate to change its state.
-    pub fn apply(&mut self, event: &IdentityAccessEvent) {
-        match event {
-            IdentityAccessEvent::RoleCreated(e) => {
-                self.id = e.role_id;
-                self.tenant_id = e.tenant_id;
-                self.name = e.name.clone();
-                self.code = e.code.clone();
-                self.description = e.description.clone();
-            }
-            IdentityAccessEvent::RoleUpdated(e) => {
-                if let Some(ref name) = e.name {
-                    self.name = name.clone();
-                }
-                if let Some(ref description) = e.description {
-                    self.description = Some(description.clone());
-                }
-            }
-            IdentityAccessEvent::RoleDeleted(_) => {
-                // Role is deleted, but we keep the state for audit purposes
-                // In a real system, you might want to mark it as deleted
-            }
-            _ => {
-                // Other events don't affect role state
-            }
+    /// Business logic for granting a permission to the role.
+    pub fn grant_permission(&self, permission_id: Uuid) -> Result<IdentityAccessEvent> {
+        if self.deleted {
+            return Err(anyhow!("Cannot grant permissions to a deleted role"));
         }
-        self.version += 1;
+        if self.permissions.contains(&permission_id) {
+            return Err(anyhow!("Role already has this permission"));
+        }
+
+        Ok(IdentityAccessEvent::RolePermissionGranted(RolePermissionGranted {
+            role_id: self.id,
+            permission_id,
+        }))
     }
 
-    /// Reconstructs the aggregate state from a series of events.
-    pub fn from_events(events: &[IdentityAccessEvent]) -> Self {
-        let mut role = Role::default();
-        for event in events {
-            role.apply(event);
+    /// Business logic for revoking a permission from the role.
+    pub fn revoke_permission(&self, permission_id: Uuid) -> Result<IdentityAccessEvent> {
+        if !self.permissions.contains(&permission_id) {
+            return Err(anyhow!("Role does not have this permission"));
         }
-        role
-    }
 
-    // Getters
-    pub fn id(&self) -> Uuid {
-        self.id
+        Ok(IdentityAccessEvent::RolePermissionRevoked(RolePermissionRevoked {
+            role_id: self.id,
+            permission_id,
+        }))
     }
 
+    // Getters
     pub fn tenant_id(&self) -> Uuid {
         self.tenant_id
     }
@@ -127,7 +127,57 @@ impl Role {
         self.description.as_ref()
     }
 
-    pub fn version(&self) -> u64 {
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+
+    pub fn permissions(&self) -> &BTreeSet<Uuid> {
+        &self.permissions
+    }
+}
+
+impl Aggregate for Role {
+    const AGGREGATE_TYPE: &'static str = "Role";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn version(&self) -> u64 {
         self.version
     }
+
+    fn apply(&mut self, event: &IdentityAccessEvent) {
+        match event {
+            IdentityAccessEvent::RoleCreated(e) => {
+                self.id = e.role_id;
+                self.tenant_id = e.tenant_id;
+                self.name = e.name.clone();
+                self.code = e.code.clone();
+                self.description = e.description.clone();
+            }
+            IdentityAccessEvent::RoleUpdated(e) => {
+                if let Some(ref name) = e.name {
+                    self.name = name.clone();
+                }
+                if let Some(ref description) = e.description {
+                    self.description = Some(description.clone());
+                }
+            }
+            IdentityAccessEvent::RoleDeleted(_) => {
+                // Role is deleted, but we keep the state for audit purposes
+                self.deleted = true;
+            }
+            IdentityAccessEvent::RolePermissionGranted(e) => {
+                self.permissions.insert(e.permission_id);
+            }
+            IdentityAccessEvent::RolePermissionRevoked(e) => {
+                self.permissions.remove(&e.permission_id);
+            }
+            _ => {
+                // Other events don't affect role state
+            }
+        }
+        self.version += 1;
+    }
 }
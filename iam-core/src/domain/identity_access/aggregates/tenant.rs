@@ -0,0 +1,153 @@
+use uuid::Uuid;
+use crate::domain::identity_access::events::{
+    IdentityAccessEvent, TenantCreated, TenantRenamed, TenantSuspended, TenantReactivated
+};
+use crate::domain::identity_access::aggregates::aggregate::Aggregate;
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+/// The state of the Tenant aggregate.
+/// It is serializable so that it can be stored as a snapshot.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Tenant {
+    id: Uuid,
+    name: String,
+    code: String,
+    status: TenantStatus,
+    version: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum TenantStatus {
+    #[default]
+    Active,
+    Suspended,
+}
+
+impl TenantStatus {
+    /// The status as stored in the `tenants` table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TenantStatus::Active => "active",
+            TenantStatus::Suspended => "suspended",
+        }
+    }
+}
+
+impl Tenant {
+    /// Business logic for creating a new tenant.
+    /// The code doubles as the tenant's subdomain, so it is limited to a lowercase DNS label.
+    pub fn create(id: Uuid, name: String, code: String) -> Result<IdentityAccessEvent> {
+        if name.trim().is_empty() {
+            return Err(anyhow!("Tenant name cannot be empty"));
+        }
+        if code.is_empty() || code.len() > 63 {
+            return Err(anyhow!("Tenant code must be between 1 and 63 characters"));
+        }
+        if !code.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            || code.starts_with('-')
+            || code.ends_with('-')
+        {
+            return Err(anyhow!(
+                "Tenant code can only contain lowercase letters, digits and inner hyphens"
+            ));
+        }
+
+        Ok(IdentityAccessEvent::TenantCreated(TenantCreated {
+            tenant_id: id,
+            name,
+            code,
+        }))
+    }
+
+    /// Business logic for renaming a tenant. The code never changes.
+    pub fn rename(&self, name: String) -> Result<IdentityAccessEvent> {
+        if name.trim().is_empty() {
+            return Err(anyhow!("Tenant name cannot be empty"));
+        }
+        if name == self.name {
+            return Err(anyhow!("Tenant already has this name"));
+        }
+
+        Ok(IdentityAccessEvent::TenantRenamed(TenantRenamed {
+            tenant_id: self.id,
+            name,
+        }))
+    }
+
+    /// Business logic for suspending a tenant, which blocks its users from logging in.
+    pub fn suspend(&self, reason: String) -> Result<IdentityAccessEvent> {
+        if self.status == TenantStatus::Suspended {
+            return Err(anyhow!("Tenant is already suspended"));
+        }
+        if reason.is_empty() {
+            return Err(anyhow!("Suspension reason cannot be empty"));
+        }
+
+        Ok(IdentityAccessEvent::TenantSuspended(TenantSuspended {
+            tenant_id: self.id,
+            reason,
+        }))
+    }
+
+    /// Business logic for reactivating a suspended tenant.
+    pub fn reactivate(&self, reason: String) -> Result<IdentityAccessEvent> {
+        if self.status != TenantStatus::Suspended {
+            return Err(anyhow!("Only suspended tenants can be reactivated"));
+        }
+
+        Ok(IdentityAccessEvent::TenantReactivated(TenantReactivated {
+            tenant_id: self.id,
+            reason,
+        }))
+    }
+
+    // Getters
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn status(&self) -> &TenantStatus {
+        &self.status
+    }
+}
+
+impl Aggregate for Tenant {
+    const AGGREGATE_TYPE: &'static str = "Tenant";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn apply(&mut self, event: &IdentityAccessEvent) {
+        match event {
+            IdentityAccessEvent::TenantCreated(e) => {
+                self.id = e.tenant_id;
+                self.name = e.name.clone();
+                self.code = e.code.clone();
+                self.status = TenantStatus::Active;
+            }
+            IdentityAccessEvent::TenantRenamed(e) => {
+                self.name = e.name.clone();
+            }
+            IdentityAccessEvent::TenantSuspended(_) => {
+                self.status = TenantStatus::Suspended;
+            }
+            IdentityAccessEvent::TenantReactivated(_) => {
+                self.status = TenantStatus::Active;
+            }
+            _ => {
+                // Other events don't affect tenant state
+            }
+        }
+        self.version += 1;
+    }
+}
@@ -1,11 +1,18 @@
 use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeSet;
 use crate::domain::identity_access::events::{
-    IdentityAccessEvent, UserRegistered, UserUpdated, UserDeactivated
+    IdentityAccessEvent, UserRegistered, UserUpdated, UserDeactivated, UserReactivated,
+    UserLocked, UserUnlocked, UserRoleAssigned, UserRoleRemoved,
+    UserOrganizationAssigned, UserOrganizationRemoved
 };
+use crate::domain::identity_access::aggregates::aggregate::Aggregate;
 use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
 
 /// The state of the User aggregate.
-#[derive(Debug, Default)]
+/// It is serializable so that it can be stored as a snapshot.
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct User {
     id: Uuid,
     tenant_id: Uuid,
@@ -13,10 +20,16 @@ pub struct User {
     email: String,
     password_hash: String,
     status: UserStatus,
+    #[serde(default)]
+    roles: BTreeSet<Uuid>,
+    #[serde(default)]
+    organizations: BTreeSet<Uuid>,
+    #[serde(default)]
+    locked_until: Option<DateTime<Utc>>,
     version: u64,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum UserStatus {
     Active,
     Inactive,
@@ -63,7 +76,7 @@ impl User {
 
     /// Business logic for updating a user.
     pub fn update(&self, username: Option<String>, email: Option<String>) -> Result<IdentityAccessEvent> {
-        if self.status != UserStatus::Active {
+        if self.current_status() != UserStatus::Active {
             return Err(anyhow!("Cannot update inactive or locked user"));
         }
 
@@ -88,7 +101,7 @@ impl User {
 
     /// Business logic for deactivating a user.
     pub fn deactivate(&self, reason: String) -> Result<IdentityAccessEvent> {
-        if self.status != UserStatus::Active {
+        if self.current_status() != UserStatus::Active {
             return Err(anyhow!("User is already inactive or locked"));
         }
 
@@ -98,50 +111,109 @@ impl User {
         }))
     }
 
-    /// Applies an event to the aggregate to change its state.
-    /// This is how we reconstruct the state from an event stream.
-    pub fn apply(&mut self, event: &IdentityAccessEvent) {
-        match event {
-            IdentityAccessEvent::UserRegistered(e) => {
-                self.id = e.user_id;
-                self.tenant_id = e.tenant_id;
-                self.username = e.username.clone();
-                self.email = e.email.clone();
-                self.password_hash = e.password_hash.clone();
-                self.status = UserStatus::Active;
-            }
-            IdentityAccessEvent::UserUpdated(e) => {
-                if let Some(ref username) = e.username {
-                    self.username = username.clone();
-                }
-                if let Some(ref email) = e.email {
-                    self.email = email.clone();
-                }
-            }
-            IdentityAccessEvent::UserDeactivated(_) => {
-                self.status = UserStatus::Inactive;
-            }
-            _ => {
-                // Other events don't affect user state
-            }
+    /// Business logic for reactivating a deactivated user.
+    pub fn reactivate(&self, reason: String) -> Result<IdentityAccessEvent> {
+        if self.status != UserStatus::Inactive {
+            return Err(anyhow!("Only inactive users can be reactivated"));
         }
-        self.version += 1;
+
+        Ok(IdentityAccessEvent::UserReactivated(UserReactivated {
+            user_id: self.id,
+            reason,
+        }))
+    }
+
+    /// Business logic for locking the user's account.
+    /// An expired lock may be replaced by a new one; an active lock must be unlocked first.
+    pub fn lock(&self, reason: String, locked_until: Option<DateTime<Utc>>, locked_by: Option<Uuid>) -> Result<IdentityAccessEvent> {
+        match self.current_status() {
+            UserStatus::Inactive => return Err(anyhow!("Cannot lock an inactive user")),
+            UserStatus::Locked => return Err(anyhow!("User is already locked")),
+            UserStatus::Active => {}
+        }
+        if reason.is_empty() {
+            return Err(anyhow!("Lock reason cannot be empty"));
+        }
+        if locked_until.is_some_and(|until| until <= Utc::now()) {
+            return Err(anyhow!("Lock expiry must be in the future"));
+        }
+
+        Ok(IdentityAccessEvent::UserLocked(UserLocked {
+            user_id: self.id,
+            reason,
+            locked_until,
+            locked_by,
+        }))
     }
 
-    /// Reconstructs the aggregate state from a series of events.
-    pub fn from_events(events: &[IdentityAccessEvent]) -> Self {
-        let mut user = User::default();
-        for event in events {
-            user.apply(event);
+    /// Business logic for unlocking the user's account, including one whose lock has expired.
+    pub fn unlock(&self, reason: String, unlocked_by: Option<Uuid>) -> Result<IdentityAccessEvent> {
+        if self.status != UserStatus::Locked {
+            return Err(anyhow!("User is not locked"));
         }
-        user
+
+        Ok(IdentityAccessEvent::UserUnlocked(UserUnlocked {
+            user_id: self.id,
+            reason,
+            unlocked_by,
+        }))
     }
 
-    // Getters
-    pub fn id(&self) -> Uuid {
-        self.id
+    /// Business logic for assigning a role to the user.
+    pub fn assign_role(&self, role_id: Uuid) -> Result<IdentityAccessEvent> {
+        if self.status == UserStatus::Inactive {
+            return Err(anyhow!("Cannot assign roles to an inactive user"));
+        }
+        if self.roles.contains(&role_id) {
+            return Err(anyhow!("User already has this role"));
+        }
+
+        Ok(IdentityAccessEvent::UserRoleAssigned(UserRoleAssigned {
+            user_id: self.id,
+            role_id,
+        }))
+    }
+
+    /// Business logic for removing a role from the user.
+    pub fn remove_role(&self, role_id: Uuid) -> Result<IdentityAccessEvent> {
+        if !self.roles.contains(&role_id) {
+            return Err(anyhow!("User does not have this role"));
+        }
+
+        Ok(IdentityAccessEvent::UserRoleRemoved(UserRoleRemoved {
+            user_id: self.id,
+            role_id,
+        }))
+    }
+
+    /// Business logic for assigning the user to an organization.
+    pub fn assign_organization(&self, organization_id: Uuid) -> Result<IdentityAccessEvent> {
+        if self.status == UserStatus::Inactive {
+            return Err(anyhow!("Cannot assign an inactive user to an organization"));
+        }
+        if self.organizations.contains(&organization_id) {
+            return Err(anyhow!("User already belongs to this organization"));
+        }
+
+        Ok(IdentityAccessEvent::UserOrganizationAssigned(UserOrganizationAssigned {
+            user_id: self.id,
+            organization_id,
+        }))
+    }
+
+    /// Business logic for removing the user from an organization.
+    pub fn remove_organization(&self, organization_id: Uuid) -> Result<IdentityAccessEvent> {
+        if !self.organizations.contains(&organization_id) {
+            return Err(anyhow!("User does not belong to this organization"));
+        }
+
+        Ok(IdentityAccessEvent::UserOrganizationRemoved(UserOrganizationRemoved {
+            user_id: self.id,
+            organization_id,
+        }))
     }
 
+    // Getters
     pub fn tenant_id(&self) -> Uuid {
         self.tenant_id
     }
@@ -158,7 +230,92 @@ impl User {
         &self.status
     }
 
-    pub fn version(&self) -> u64 {
+    pub fn locked_until(&self) -> Option<DateTime<Utc>> {
+        self.locked_until
+    }
+
+    /// Returns whether the account is locked at `now`; a lock past its expiry no longer counts.
+    pub fn is_locked_at(&self, now: DateTime<Utc>) -> bool {
+        self.status == UserStatus::Locked && self.locked_until.is_none_or(|until| until > now)
+    }
+
+    /// The status the business rules apply, with an expired lock treated as active.
+    fn current_status(&self) -> UserStatus {
+        match self.status {
+            UserStatus::Locked if !self.is_locked_at(Utc::now()) => UserStatus::Active,
+            ref status => status.clone(),
+        }
+    }
+
+    pub fn roles(&self) -> &BTreeSet<Uuid> {
+        &self.roles
+    }
+
+    pub fn organizations(&self) -> &BTreeSet<Uuid> {
+        &self.organizations
+    }
+}
+
+impl Aggregate for User {
+    const AGGREGATE_TYPE: &'static str = "User";
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn version(&self) -> u64 {
         self.version
     }
+
+    fn apply(&mut self, event: &IdentityAccessEvent) {
+        match event {
+            IdentityAccessEvent::UserRegistered(e) => {
+                self.id = e.user_id;
+                self.tenant_id = e.tenant_id;
+                self.username = e.username.clone();
+                self.email = e.email.clone();
+                self.password_hash = e.password_hash.clone();
+                self.status = UserStatus::Active;
+            }
+            IdentityAccessEvent::UserUpdated(e) => {
+                if let Some(ref username) = e.username {
+                    self.username = username.clone();
+                }
+                if let Some(ref email) = e.email {
+                    self.email = email.clone();
+                }
+            }
+            IdentityAccessEvent::UserDeactivated(_) => {
+                self.status = UserStatus::Inactive;
+                self.locked_until = None;
+            }
+            IdentityAccessEvent::UserReactivated(_) => {
+                self.status = UserStatus::Active;
+            }
+            IdentityAccessEvent::UserLocked(e) => {
+                self.status = UserStatus::Locked;
+                self.locked_until = e.locked_until;
+            }
+            IdentityAccessEvent::UserUnlocked(_) => {
+                self.status = UserStatus::Active;
+                self.locked_until = None;
+            }
+            IdentityAccessEvent::UserRoleAssigned(e) => {
+                self.roles.insert(e.role_id);
+            }
+            IdentityAccessEvent::UserRoleRemoved(e) => {
+                self.roles.remove(&e.role_id);
+            }
+            IdentityAccessEvent::UserOrganizationAssigned(e) => {
+                self.organizations.insert(e.organization_id);
+            }
+            IdentityAccessEvent::UserOrganizationRemoved(e) => {
+                self.organizations.remove(&e.organization_id);
+            }
+            _ => {
+                // Other events don't affect user state
+            }
+        }
+        self.version += 1;
+    }
 }
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 /// Command to register a new user.
@@ -24,6 +25,30 @@ pub struct DeactivateUserCommand {
     pub reason: String,
 }
 
+/// Command to lock a user's account, until `locked_until` or until it is unlocked.
+#[derive(Debug)]
+pub struct LockUserCommand {
+    pub user_id: Uuid,
+    pub reason: String,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub locked_by: Option<Uuid>,
+}
+
+/// Command to unlock a locked user's account.
+#[derive(Debug)]
+pub struct UnlockUserCommand {
+    pub user_id: Uuid,
+    pub reason: String,
+    pub unlocked_by: Option<Uuid>,
+}
+
+/// Command to reactivate a deactivated user.
+#[derive(Debug)]
+pub struct ReactivateUserCommand {
+    pub user_id: Uuid,
+    pub reason: String,
+}
+
 /// Command to create a new role.
 #[derive(Debug)]
 pub struct CreateRoleCommand {
@@ -60,3 +85,121 @@ pub struct RemoveUserRoleCommand {
     pub user_id: Uuid,
     pub role_id: Uuid,
 }
+
+/// Command to create a new tenant.
+#[derive(Debug)]
+pub struct CreateTenantCommand {
+    pub name: String,
+    pub code: String,
+}
+
+/// Command to rename a tenant.
+#[derive(Debug)]
+pub struct RenameTenantCommand {
+    pub tenant_id: Uuid,
+    pub name: String,
+}
+
+/// Command to suspend a tenant.
+#[derive(Debug)]
+pub struct SuspendTenantCommand {
+    pub tenant_id: Uuid,
+    pub reason: String,
+}
+
+/// Command to reactivate a suspended tenant.
+#[derive(Debug)]
+pub struct ReactivateTenantCommand {
+    pub tenant_id: Uuid,
+    pub reason: String,
+}
+
+/// Command to create a new organization.
+#[derive(Debug)]
+pub struct CreateOrganizationCommand {
+    pub tenant_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    pub code: String,
+}
+
+/// Command to rename an organization.
+#[derive(Debug)]
+pub struct RenameOrganizationCommand {
+    pub tenant_id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+}
+
+/// Command to move an organization under a new parent, or to the root when `parent_id` is `None`.
+#[derive(Debug)]
+pub struct MoveOrganizationCommand {
+    pub tenant_id: Uuid,
+    pub organization_id: Uuid,
+    pub parent_id: Option<Uuid>,
+}
+
+/// Command to delete an organization.
+#[derive(Debug)]
+pub struct DeleteOrganizationCommand {
+    pub tenant_id: Uuid,
+    pub organization_id: Uuid,
+}
+
+/// Command to assign a user to an organization.
+#[derive(Debug)]
+pub struct AssignUserOrganizationCommand {
+    pub tenant_id: Uuid,
+    pub user_id: Uuid,
+    pub organization_id: Uuid,
+}
+
+/// Command to remove a user from an organization.
+#[derive(Debug)]
+pub struct RemoveUserOrganizationCommand {
+    pub tenant_id: Uuid,
+    pub user_id: Uuid,
+    pub organization_id: Uuid,
+}
+
+/// Command to create a new permission in the catalog.
+#[derive(Debug)]
+pub struct CreatePermissionCommand {
+    pub parent_id: Option<Uuid>,
+    pub permission_type: String,
+    pub name: String,
+    pub code: String,
+    pub route: Option<String>,
+    pub icon: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Command to update a permission.
+#[derive(Debug)]
+pub struct UpdatePermissionCommand {
+    pub permission_id: Uuid,
+    pub name: Option<String>,
+    pub route: Option<String>,
+    pub icon: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Command to delete a permission.
+#[derive(Debug)]
+pub struct DeletePermissionCommand {
+    pub permission_id: Uuid,
+}
+
+/// Command to grant a permission to a role.
+#[derive(Debug)]
+pub struct GrantRolePermissionCommand {
+    pub role_id: Uuid,
+    pub permission_id: Uuid,
+}
+
+/// Command to revoke a permission from a role.
+#[derive(Debug)]
+pub struct RevokeRolePermissionCommand {
+    pub role_id: Uuid,
+    pub permission_id: Uuid,
+}
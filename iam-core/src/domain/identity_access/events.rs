@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -7,14 +8,97 @@ pub enum IdentityAccessEvent {
     UserRegistered(UserRegistered),
     UserUpdated(UserUpdated),
     UserDeactivated(UserDeactivated),
+    UserReactivated(UserReactivated),
+    UserLocked(UserLocked),
+    UserUnlocked(UserUnlocked),
     RoleCreated(RoleCreated),
     RoleUpdated(RoleUpdated),
     RoleDeleted(RoleDeleted),
     PermissionCreated(PermissionCreated),
+    PermissionUpdated(PermissionUpdated),
+    PermissionDeleted(PermissionDeleted),
     UserRoleAssigned(UserRoleAssigned),
     UserRoleRemoved(UserRoleRemoved),
     RolePermissionGranted(RolePermissionGranted),
     RolePermissionRevoked(RolePermissionRevoked),
+    TenantCreated(TenantCreated),
+    TenantRenamed(TenantRenamed),
+    TenantSuspended(TenantSuspended),
+    TenantReactivated(TenantReactivated),
+    OrganizationCreated(OrganizationCreated),
+    OrganizationRenamed(OrganizationRenamed),
+    OrganizationMoved(OrganizationMoved),
+    OrganizationDeleted(OrganizationDeleted),
+    UserOrganizationAssigned(UserOrganizationAssigned),
+    UserOrganizationRemoved(UserOrganizationRemoved),
+}
+
+impl IdentityAccessEvent {
+    /// Returns the name under which the event is recorded in the event store.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            IdentityAccessEvent::UserRegistered(_) => "UserRegistered",
+            IdentityAccessEvent::UserUpdated(_) => "UserUpdated",
+            IdentityAccessEvent::UserDeactivated(_) => "UserDeactivated",
+            IdentityAccessEvent::UserReactivated(_) => "UserReactivated",
+            IdentityAccessEvent::UserLocked(_) => "UserLocked",
+            IdentityAccessEvent::UserUnlocked(_) => "UserUnlocked",
+            IdentityAccessEvent::RoleCreated(_) => "RoleCreated",
+            IdentityAccessEvent::RoleUpdated(_) => "RoleUpdated",
+            IdentityAccessEvent::RoleDeleted(_) => "RoleDeleted",
+            IdentityAccessEvent::PermissionCreated(_) => "PermissionCreated",
+            IdentityAccessEvent::PermissionUpdated(_) => "PermissionUpdated",
+            IdentityAccessEvent::PermissionDeleted(_) => "PermissionDeleted",
+            IdentityAccessEvent::UserRoleAssigned(_) => "UserRoleAssigned",
+            IdentityAccessEvent::UserRoleRemoved(_) => "UserRoleRemoved",
+            IdentityAccessEvent::RolePermissionGranted(_) => "RolePermissionGranted",
+            IdentityAccessEvent::RolePermissionRevoked(_) => "RolePermissionRevoked",
+            IdentityAccessEvent::TenantCreated(_) => "TenantCreated",
+            IdentityAccessEvent::TenantRenamed(_) => "TenantRenamed",
+            IdentityAccessEvent::TenantSuspended(_) => "TenantSuspended",
+            IdentityAccessEvent::TenantReactivated(_) => "TenantReactivated",
+            IdentityAccessEvent::OrganizationCreated(_) => "OrganizationCreated",
+            IdentityAccessEvent::OrganizationRenamed(_) => "OrganizationRenamed",
+            IdentityAccessEvent::OrganizationMoved(_) => "OrganizationMoved",
+            IdentityAccessEvent::OrganizationDeleted(_) => "OrganizationDeleted",
+            IdentityAccessEvent::UserOrganizationAssigned(_) => "UserOrganizationAssigned",
+            IdentityAccessEvent::UserOrganizationRemoved(_) => "UserOrganizationRemoved",
+        }
+    }
+
+    /// Returns the version of the payload shape this event is serialized with.
+    /// Bump it whenever the fields of an event change, and register an upcaster
+    /// that migrates payloads stored with the previous version.
+    pub fn schema_version(&self) -> u32 {
+        match self {
+            IdentityAccessEvent::UserRegistered(_)
+            | IdentityAccessEvent::UserUpdated(_)
+            | IdentityAccessEvent::UserDeactivated(_)
+            | IdentityAccessEvent::UserReactivated(_)
+            | IdentityAccessEvent::UserLocked(_)
+            | IdentityAccessEvent::UserUnlocked(_)
+            | IdentityAccessEvent::RoleCreated(_)
+            | IdentityAccessEvent::RoleUpdated(_)
+            | IdentityAccessEvent::RoleDeleted(_)
+            | IdentityAccessEvent::PermissionCreated(_)
+            | IdentityAccessEvent::PermissionUpdated(_)
+            | IdentityAccessEvent::PermissionDeleted(_)
+            | IdentityAccessEvent::UserRoleAssigned(_)
+            | IdentityAccessEvent::UserRoleRemoved(_)
+            | IdentityAccessEvent::RolePermissionGranted(_)
+            | IdentityAccessEvent::RolePermissionRevoked(_)
+            | IdentityAccessEvent::TenantCreated(_)
+            | IdentityAccessEvent::TenantRenamed(_)
+            | IdentityAccessEvent::TenantSuspended(_)
+            | IdentityAccessEvent::TenantReactivated(_)
+            | IdentityAccessEvent::OrganizationCreated(_)
+            | IdentityAccessEvent::OrganizationRenamed(_)
+            | IdentityAccessEvent::OrganizationMoved(_)
+            | IdentityAccessEvent::OrganizationDeleted(_)
+            | IdentityAccessEvent::UserOrganizationAssigned(_)
+            | IdentityAccessEvent::UserOrganizationRemoved(_) => 1,
+        }
+    }
 }
 
 /// Event indicating that a new user has registered.
@@ -42,6 +126,33 @@ pub struct UserDeactivated {
     pub reason: String,
 }
 
+/// Event indicating that a deactivated user has been reactivated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserReactivated {
+    pub user_id: Uuid,
+    pub reason: String,
+}
+
+/// Event indicating that a user's account has been locked.
+/// Without `locked_until` the lock lasts until the account is explicitly unlocked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserLocked {
+    pub user_id: Uuid,
+    pub reason: String,
+    pub locked_until: Option<DateTime<Utc>>,
+    /// The user who locked the account, `None` when the system locked it.
+    pub locked_by: Option<Uuid>,
+}
+
+/// Event indicating that a locked account has been unlocked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserUnlocked {
+    pub user_id: Uuid,
+    pub reason: String,
+    /// The user who unlocked the account, `None` when the system unlocked it.
+    pub unlocked_by: Option<Uuid>,
+}
+
 /// Event indicating that a new role has been created.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RoleCreated {
@@ -79,6 +190,22 @@ pub struct PermissionCreated {
     pub description: Option<String>,
 }
 
+/// Event indicating that a permission has been updated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PermissionUpdated {
+    pub permission_id: Uuid,
+    pub name: Option<String>,
+    pub route: Option<String>,
+    pub icon: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Event indicating that a permission has been deleted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PermissionDeleted {
+    pub permission_id: Uuid,
+}
+
 /// Event indicating that a role has been assigned to a user.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UserRoleAssigned {
@@ -106,3 +233,77 @@ pub struct RolePermissionRevoked {
     pub role_id: Uuid,
     pub permission_id: Uuid,
 }
+
+/// Event indicating that a new tenant has been created.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TenantCreated {
+    pub tenant_id: Uuid,
+    pub name: String,
+    pub code: String,
+}
+
+/// Event indicating that a tenant has been renamed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TenantRenamed {
+    pub tenant_id: Uuid,
+    pub name: String,
+}
+
+/// Event indicating that a tenant has been suspended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TenantSuspended {
+    pub tenant_id: Uuid,
+    pub reason: String,
+}
+
+/// Event indicating that a suspended tenant has been reactivated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TenantReactivated {
+    pub tenant_id: Uuid,
+    pub reason: String,
+}
+
+/// Event indicating that a new organization has been created.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationCreated {
+    pub organization_id: Uuid,
+    pub tenant_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    pub code: String,
+}
+
+/// Event indicating that an organization has been renamed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationRenamed {
+    pub organization_id: Uuid,
+    pub name: String,
+}
+
+/// Event indicating that an organization, with its subtree, has been moved under a new parent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationMoved {
+    pub organization_id: Uuid,
+    pub previous_parent_id: Option<Uuid>,
+    pub parent_id: Option<Uuid>,
+}
+
+/// Event indicating that an organization has been deleted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationDeleted {
+    pub organization_id: Uuid,
+}
+
+/// Event indicating that a user has been assigned to an organization.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserOrganizationAssigned {
+    pub user_id: Uuid,
+    pub organization_id: Uuid,
+}
+
+/// Event indicating that a user has been removed from an organization.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserOrganizationRemoved {
+    pub user_id: Uuid,
+    pub organization_id: Uuid,
+}
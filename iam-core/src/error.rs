@@ -33,9 +33,18 @@ pub enum AppError {
     #[error("Authorization failed: {0}")]
     AuthorizationError(String),
 
+    #[error("Account locked: {0}")]
+    AccountLocked(String),
+
+    #[error("Too many requests: {0}")]
+    RateLimited(String),
+
     #[error("Resource not found: {0}")]
     NotFound(String),
 
+    #[error("Resource already exists: {0}")]
+    Conflict(String),
+
     #[error("Internal server error: {0}")]
     InternalError(String),
 
@@ -60,7 +69,10 @@ impl IntoResponse for AppError {
             }
             AppError::AuthenticationError(msg) => (StatusCode::UNAUTHORIZED, msg),
             AppError::AuthorizationError(msg) => (StatusCode::FORBIDDEN, msg),
+            AppError::AccountLocked(msg) => (StatusCode::LOCKED, msg),
+            AppError::RateLimited(msg) => (StatusCode::TOO_MANY_REQUESTS, msg),
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg),
             AppError::ConcurrencyConflict => {
                 (StatusCode::CONFLICT, "Concurrency conflict: Resource was modified by another request".to_string())
             }
@@ -1,3 +1,5 @@
 pub mod persistence;
+pub mod security;
 
 pub use persistence::*;
+pub use security::*;
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+use tokio::sync::RwLock;
+
+use crate::error::AppError;
+
+/// An API permission and the route it protects, as `<METHOD> <path template>`.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct ApiRoute {
+    pub code: String,
+    pub route: String,
+}
+
+/// Trait for reading the routes of the API permissions in the catalog.
+#[async_trait]
+pub trait ApiPermissionStore: Send + Sync {
+    /// Finds the routes of every API permission that has not been deleted.
+    async fn find_api_routes(&self) -> Result<Vec<ApiRoute>, AppError>;
+}
+
+pub struct SqlxApiPermissionStore {
+    pool: MySqlPool,
+}
+
+impl SqlxApiPermissionStore {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ApiPermissionStore for SqlxApiPermissionStore {
+    async fn find_api_routes(&self) -> Result<Vec<ApiRoute>, AppError> {
+        let routes = sqlx::query_as::<_, ApiRoute>(
+            "SELECT code, route FROM permissions_view \
+             WHERE permission_type = 'api' AND deleted = FALSE AND route IS NOT NULL"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(routes)
+    }
+}
+
+/// An `ApiPermissionStore` kept in memory, for tests and local development.
+#[derive(Default)]
+pub struct InMemoryApiPermissionStore {
+    routes: RwLock<Vec<ApiRoute>>,
+}
+
+impl InMemoryApiPermissionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn save_route(&self, route: ApiRoute) {
+        self.routes.write().await.push(route);
+    }
+}
+
+#[async_trait]
+impl ApiPermissionStore for InMemoryApiPermissionStore {
+    async fn find_api_routes(&self) -> Result<Vec<ApiRoute>, AppError> {
+        Ok(self.routes.read().await.clone())
+    }
+}
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// A permission a user holds, as stored in the `user_effective_permissions` table, together
+/// with the role and the grant it comes from. A user holds a permission once per grant that
+/// covers it, either directly or through a menu above it.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct EffectivePermission {
+    pub permission_id: Uuid,
+    pub code: String,
+    pub role_id: Uuid,
+    pub granted_permission_id: Uuid,
+}
+
+/// Trait for reading the permissions users hold, as resolved by the effective permission projection.
+#[async_trait]
+pub trait EffectivePermissionStore: Send + Sync {
+    async fn find_user_permissions(&self, user_id: Uuid) -> Result<Vec<EffectivePermission>, AppError>;
+
+    /// Finds the grants through which the user holds the permission with `code`, if any.
+    async fn find_user_permission(&self, user_id: Uuid, code: &str) -> Result<Vec<EffectivePermission>, AppError>;
+}
+
+pub struct SqlxEffectivePermissionStore {
+    pool: MySqlPool,
+}
+
+impl SqlxEffectivePermissionStore {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EffectivePermissionStore for SqlxEffectivePermissionStore {
+    async fn find_user_permissions(&self, user_id: Uuid) -> Result<Vec<EffectivePermission>, AppError> {
+        let permissions = sqlx::query_as::<_, EffectivePermission>(
+            "SELECT permission_id, code, role_id, granted_permission_id FROM user_effective_permissions \
+             WHERE user_id = ? ORDER BY code"
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(permissions)
+    }
+
+    async fn find_user_permission(&self, user_id: Uuid, code: &str) -> Result<Vec<EffectivePermission>, AppError> {
+        let permissions = sqlx::query_as::<_, EffectivePermission>(
+            "SELECT permission_id, code, role_id, granted_permission_id FROM user_effective_permissions \
+             WHERE user_id = ? AND code = ?"
+        )
+        .bind(user_id)
+        .bind(code)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(permissions)
+    }
+}
+
+/// An `EffectivePermissionStore` kept in memory, for tests and local development.
+#[derive(Default)]
+pub struct InMemoryEffectivePermissionStore {
+    permissions: RwLock<HashMap<Uuid, Vec<EffectivePermission>>>,
+}
+
+impl InMemoryEffectivePermissionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn save_permission(&self, user_id: Uuid, permission: EffectivePermission) {
+        self.permissions.write().await.entry(user_id).or_default().push(permission);
+    }
+}
+
+#[async_trait]
+impl EffectivePermissionStore for InMemoryEffectivePermissionStore {
+    async fn find_user_permissions(&self, user_id: Uuid) -> Result<Vec<EffectivePermission>, AppError> {
+        let mut permissions = self.permissions.read().await.get(&user_id).cloned().unwrap_or_default();
+        permissions.sort_by(|a, b| a.code.cmp(&b.code));
+        Ok(permissions)
+    }
+
+    async fn find_user_permission(&self, user_id: Uuid, code: &str) -> Result<Vec<EffectivePermission>, AppError> {
+        Ok(self
+            .permissions
+            .read()
+            .await
+            .get(&user_id)
+            .map(|permissions| permissions.iter().filter(|permission| permission.code == code).cloned().collect())
+            .unwrap_or_default())
+    }
+}
@@ -1,17 +1,33 @@
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::domain::identity_access::events::IdentityAccessEvent;
 use crate::error::AppError;
+use crate::infrastructure::persistence::upcasting::{UpcasterRegistry, identity_access_upcasters};
 
 /// Trait for an event store.
 #[async_trait]
 pub trait EventStore: Send + Sync {
-    /// Saves events to the store for a given aggregate.
+    /// Saves events to the store for a given aggregate, each one carrying `metadata`.
     /// This operation must be atomic.
-    async fn save_events(&self, aggregate_id: Uuid, events: &[IdentityAccessEvent], expected_version: u64) -> Result<(), AppError>;
+    async fn save_events(&self, aggregate_id: Uuid, events: &[IdentityAccessEvent], expected_version: u64, metadata: &EventMetadata) -> Result<(), AppError>;
 
     /// Loads all events for a given aggregate.
     async fn load_events(&self, aggregate_id: Uuid) -> Result<Vec<StoredEvent>, AppError>;
+
+    /// Loads the events of an aggregate with a sequence greater than `version`.
+    /// Unlike `load_events`, an empty result is not an error.
+    async fn load_events_after(&self, aggregate_id: Uuid, version: u64) -> Result<Vec<StoredEvent>, AppError>;
+
+    /// Reads up to `limit` events across all aggregates in global order,
+    /// starting after `from_position` (pass 0 to read from the beginning).
+    async fn read_all(&self, from_position: u64, limit: u64) -> Result<Vec<StoredEvent>, AppError>;
+
+    /// Same as `read_all`, restricted to the given event types.
+    async fn read_all_by_type(&self, event_types: &[&str], from_position: u64, limit: u64) -> Result<Vec<StoredEvent>, AppError>;
+
+    /// The position of the newest event in the store, 0 if it is empty.
+    async fn latest_position(&self) -> Result<u64, AppError>;
 }
 
 /// Represents an event as it is stored in the database.
@@ -20,12 +36,52 @@ pub struct StoredEvent {
     pub id: Uuid,
     pub aggregate_id: Uuid,
     pub sequence: u64,
+    /// Position of the event in the global stream; increases monotonically across all aggregates.
+    pub position: u64,
     pub event_type: String,
+    /// Version of the payload shape; raised by upcasters when the event is read.
+    pub schema_version: u32,
     pub payload: serde_json::Value,
+    #[sqlx(json)]
+    pub metadata: EventMetadata,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
-// We can now implement the `EventStore` trait for `SqlxEventStore` or a `InMemoryEventStore` for tests.
+/// Context recorded with every event: who caused it, for which tenant and as part of which request.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventMetadata {
+    /// The authenticated user who issued the command.
+    pub actor_id: Option<Uuid>,
+    /// The tenant the command was issued in.
+    pub tenant_id: Option<Uuid>,
+    /// Identifies the request (or workflow) that all resulting events belong to.
+    pub correlation_id: Option<String>,
+    /// The event that triggered this one, when it was not caused directly by a request.
+    pub causation_id: Option<Uuid>,
+    pub client_ip: Option<String>,
+}
+
+impl EventMetadata {
+    /// Metadata for events emitted in reaction to `event`: same actor, tenant and correlation,
+    /// with `event` as the cause.
+    pub fn caused_by(event: &StoredEvent) -> Self {
+        Self {
+            causation_id: Some(event.id),
+            ..event.metadata.clone()
+        }
+    }
+}
+
+/// Deserializes stored events back into domain events.
+pub fn deserialize_events(stored_events: &[StoredEvent]) -> Result<Vec<IdentityAccessEvent>, AppError> {
+    stored_events
+        .iter()
+        .map(|stored_event| serde_json::from_value(stored_event.payload.clone()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(AppError::SerializationError)
+}
+
+// The trait is implemented by `SqlxEventStore` below and by `InMemoryEventStore` for tests and local development.
 
 /*
 -- SQL for creating the events table in MySQL:
@@ -36,25 +92,58 @@ CREATE TABLE events (
     event_type VARCHAR(255) NOT NULL,
     payload JSON NOT NULL,
     created_at TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
-    UNIQUE KEY uq_aggregate_sequence (aggregate_id, sequence)
+    position BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
+    metadata JSON NOT NULL,
+    schema_version INT UNSIGNED NOT NULL DEFAULT 1,
+    UNIQUE KEY uq_aggregate_sequence (aggregate_id, sequence),
+    UNIQUE KEY uq_events_position (position)
 );
 */
 
 use sqlx::{MySqlPool, Row};
 
+const EVENT_COLUMNS: &str = "id, aggregate_id, sequence, position, event_type, schema_version, payload, metadata, created_at";
+
+/// Whether `error` is MySQL's duplicate-key error (1062) on the unique index named `index`.
+pub(crate) fn is_duplicate_key(error: &sqlx::Error, index: &str) -> bool {
+    match error {
+        sqlx::Error::Database(e) => e
+            .try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>()
+            .is_some_and(|e| e.number() == 1062 && e.message().contains(index)),
+        _ => false,
+    }
+}
+
 pub struct SqlxEventStore {
     pool: MySqlPool,
+    upcasters: UpcasterRegistry,
 }
 
 impl SqlxEventStore {
     pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
+        Self { pool, upcasters: identity_access_upcasters() }
+    }
+
+    /// Replaces the upcasters applied to every event read from the store.
+    pub fn with_upcasters(mut self, upcasters: UpcasterRegistry) -> Self {
+        self.upcasters = upcasters;
+        self
+    }
+
+    async fn fetch_events<'q>(
+        &self,
+        query: sqlx::query::QueryAs<'q, sqlx::MySql, StoredEvent, sqlx::mysql::MySqlArguments>,
+    ) -> Result<Vec<StoredEvent>, AppError> {
+        let mut stored_events = query.fetch_all(&self.pool).await?;
+        self.upcasters.upcast_all(&mut stored_events)?;
+        Ok(stored_events)
     }
 }
 
 #[async_trait]
 impl EventStore for SqlxEventStore {
-    async fn save_events(&self, aggregate_id: Uuid, events: &[IdentityAccessEvent], expected_version: u64) -> Result<(), AppError> {
+    async fn save_events(&self, aggregate_id: Uuid, events: &[IdentityAccessEvent], expected_version: u64, metadata: &EventMetadata) -> Result<(), AppError> {
+        let metadata = serde_json::to_value(metadata)?;
         let mut tx = self.pool.begin().await?;
 
         let current_version: Option<i64> = sqlx::query("SELECT MAX(sequence) FROM events WHERE aggregate_id = ?")
@@ -74,30 +163,29 @@ impl EventStore for SqlxEventStore {
         for event in events {
             sequence += 1;
             let payload = serde_json::to_value(event)?;
-            let event_type = match event {
-                IdentityAccessEvent::UserRegistered(_) => "UserRegistered",
-                IdentityAccessEvent::UserUpdated(_) => "UserUpdated",
-                IdentityAccessEvent::UserDeactivated(_) => "UserDeactivated",
-                IdentityAccessEvent::RoleCreated(_) => "RoleCreated",
-                IdentityAccessEvent::RoleUpdated(_) => "RoleUpdated",
-                IdentityAccessEvent::RoleDeleted(_) => "RoleDeleted",
-                IdentityAccessEvent::PermissionCreated(_) => "PermissionCreated",
-                IdentityAccessEvent::UserRoleAssigned(_) => "UserRoleAssigned",
-                IdentityAccessEvent::UserRoleRemoved(_) => "UserRoleRemoved",
-                IdentityAccessEvent::RolePermissionGranted(_) => "RolePermissionGranted",
-                IdentityAccessEvent::RolePermissionRevoked(_) => "RolePermissionRevoked",
-            };
+            let event_type = event.event_type();
 
             sqlx::query(
-                "INSERT INTO events (id, aggregate_id, sequence, event_type, payload) VALUES (?, ?, ?, ?, ?)"
+                "INSERT INTO events (id, aggregate_id, sequence, event_type, schema_version, payload, metadata) VALUES (?, ?, ?, ?, ?, ?, ?)"
             )
             .bind(Uuid::new_v4())
             .bind(aggregate_id)
             .bind(sequence)
             .bind(event_type)
+            .bind(event.schema_version())
             .bind(payload)
+            .bind(&metadata)
             .execute(&mut *tx)
-            .await?;
+            .await
+            .map_err(|e| {
+                // The version read above takes no lock, so a writer that raced us past the check
+                // is caught by the unique sequence index instead
+                if is_duplicate_key(&e, "uq_aggregate_sequence") {
+                    AppError::ConcurrencyConflict
+                } else {
+                    AppError::from(e)
+                }
+            })?;
         }
 
         tx.commit().await?;
@@ -105,12 +193,8 @@ impl EventStore for SqlxEventStore {
     }
 
     async fn load_events(&self, aggregate_id: Uuid) -> Result<Vec<StoredEvent>, AppError> {
-        let stored_events = sqlx::query_as::<_, StoredEvent>(
-            "SELECT id, aggregate_id, sequence, event_type, payload, created_at FROM events WHERE aggregate_id = ? ORDER BY sequence ASC"
-        )
-        .bind(aggregate_id)
-        .fetch_all(&self.pool)
-        .await?;
+        let sql = format!("SELECT {} FROM events WHERE aggregate_id = ? ORDER BY sequence ASC", EVENT_COLUMNS);
+        let stored_events = self.fetch_events(sqlx::query_as(&sql).bind(aggregate_id)).await?;
 
         if stored_events.is_empty() {
             return Err(AppError::AggregateNotFound(aggregate_id.to_string()));
@@ -118,4 +202,43 @@ impl EventStore for SqlxEventStore {
 
         Ok(stored_events)
     }
-}
\ No newline at end of file
+
+    async fn load_events_after(&self, aggregate_id: Uuid, version: u64) -> Result<Vec<StoredEvent>, AppError> {
+        let sql = format!("SELECT {} FROM events WHERE aggregate_id = ? AND sequence > ? ORDER BY sequence ASC", EVENT_COLUMNS);
+        self.fetch_events(sqlx::query_as(&sql).bind(aggregate_id).bind(version)).await
+    }
+
+    // Positions come from AUTO_INCREMENT and are assigned at insert time, so a transaction that
+    // commits late can make a lower position visible after a higher one has already been read.
+    async fn read_all(&self, from_position: u64, limit: u64) -> Result<Vec<StoredEvent>, AppError> {
+        let sql = format!("SELECT {} FROM events WHERE position > ? ORDER BY position ASC LIMIT ?", EVENT_COLUMNS);
+        self.fetch_events(sqlx::query_as(&sql).bind(from_position).bind(limit)).await
+    }
+
+    async fn read_all_by_type(&self, event_types: &[&str], from_position: u64, limit: u64) -> Result<Vec<StoredEvent>, AppError> {
+        if event_types.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let placeholders = vec!["?"; event_types.len()].join(", ");
+        let sql = format!(
+            "SELECT {} FROM events WHERE event_type IN ({}) AND position > ? ORDER BY position ASC LIMIT ?",
+            EVENT_COLUMNS, placeholders
+        );
+
+        let mut query = sqlx::query_as::<_, StoredEvent>(&sql);
+        for event_type in event_types {
+            query = query.bind(*event_type);
+        }
+
+        self.fetch_events(query.bind(from_position).bind(limit)).await
+    }
+
+    async fn latest_position(&self) -> Result<u64, AppError> {
+        let position: Option<u64> = sqlx::query_scalar("SELECT MAX(position) FROM events")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(position.unwrap_or(0))
+    }
+}
@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::domain::identity_access::events::IdentityAccessEvent;
+use crate::error::AppError;
+use crate::infrastructure::persistence::event_store::{EventMetadata, EventStore, StoredEvent};
+use crate::infrastructure::persistence::upcasting::{UpcasterRegistry, identity_access_upcasters};
+
+/// An `EventStore` that keeps every stream in memory.
+///
+/// It follows the same rules as `SqlxEventStore`: appends are checked against the
+/// expected version under a single write lock, and loading an empty stream yields
+/// `AppError::AggregateNotFound`. Nothing survives a restart, so it is only meant
+/// for tests and local development without a database.
+pub struct InMemoryEventStore {
+    inner: RwLock<Inner>,
+    upcasters: UpcasterRegistry,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Every event in global order; the event at index `i` has position `i + 1`.
+    log: Vec<StoredEvent>,
+    /// Indexes into `log` for each aggregate, in sequence order.
+    streams: HashMap<Uuid, Vec<usize>>,
+}
+
+impl Inner {
+    fn stream(&self, aggregate_id: Uuid) -> impl Iterator<Item = &StoredEvent> {
+        self.streams
+            .get(&aggregate_id)
+            .into_iter()
+            .flatten()
+            .map(|&index| &self.log[index])
+    }
+
+    fn read_from(&self, from_position: u64) -> impl Iterator<Item = &StoredEvent> {
+        // Positions start at 1, so the event after `from_position` sits at that index.
+        self.log.iter().skip(from_position as usize)
+    }
+}
+
+impl InMemoryEventStore {
+    pub fn new() -> Self {
+        Self {
+            inner: RwLock::new(Inner::default()),
+            upcasters: identity_access_upcasters(),
+        }
+    }
+
+    /// Replaces the upcasters applied to every event read from the store.
+    pub fn with_upcasters(mut self, upcasters: UpcasterRegistry) -> Self {
+        self.upcasters = upcasters;
+        self
+    }
+
+    fn upcasted(&self, events: impl Iterator<Item = StoredEvent>) -> Result<Vec<StoredEvent>, AppError> {
+        let mut events: Vec<StoredEvent> = events.collect();
+        self.upcasters.upcast_all(&mut events)?;
+        Ok(events)
+    }
+}
+
+impl Default for InMemoryEventStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventStore for InMemoryEventStore {
+    async fn save_events(&self, aggregate_id: Uuid, events: &[IdentityAccessEvent], expected_version: u64, metadata: &EventMetadata) -> Result<(), AppError> {
+        let mut inner = self.inner.write().await;
+
+        let current_version = inner.stream(aggregate_id).last().map(|event| event.sequence).unwrap_or(0);
+        if current_version != expected_version {
+            return Err(AppError::ConcurrencyConflict);
+        }
+
+        // Serialize everything before touching the log so a failure leaves it unchanged.
+        let first_position = inner.log.len() as u64 + 1;
+        let mut new_events = Vec::with_capacity(events.len());
+        for ((sequence, position), event) in (current_version + 1..).zip(first_position..).zip(events) {
+            new_events.push(StoredEvent {
+                id: Uuid::new_v4(),
+                aggregate_id,
+                sequence,
+                position,
+                event_type: event.event_type().to_string(),
+                schema_version: event.schema_version(),
+                payload: serde_json::to_value(event)?,
+                metadata: metadata.clone(),
+                created_at: chrono::Utc::now(),
+            });
+        }
+
+        let first_index = inner.log.len();
+        inner.log.extend(new_events);
+        let last_index = inner.log.len();
+        inner.streams.entry(aggregate_id).or_default().extend(first_index..last_index);
+
+        Ok(())
+    }
+
+    async fn load_events(&self, aggregate_id: Uuid) -> Result<Vec<StoredEvent>, AppError> {
+        let inner = self.inner.read().await;
+        let stored_events = self.upcasted(inner.stream(aggregate_id).cloned())?;
+
+        if stored_events.is_empty() {
+            return Err(AppError::AggregateNotFound(aggregate_id.to_string()));
+        }
+
+        Ok(stored_events)
+    }
+
+    async fn load_events_after(&self, aggregate_id: Uuid, version: u64) -> Result<Vec<StoredEvent>, AppError> {
+        let inner = self.inner.read().await;
+
+        self.upcasted(inner.stream(aggregate_id).filter(|event| event.sequence > version).cloned())
+    }
+
+    async fn read_all(&self, from_position: u64, limit: u64) -> Result<Vec<StoredEvent>, AppError> {
+        let inner = self.inner.read().await;
+
+        self.upcasted(inner.read_from(from_position).take(limit as usize).cloned())
+    }
+
+    async fn read_all_by_type(&self, event_types: &[&str], from_position: u64, limit: u64) -> Result<Vec<StoredEvent>, AppError> {
+        let inner = self.inner.read().await;
+
+        self.upcasted(
+            inner
+                .read_from(from_position)
+                .filter(|event| event_types.contains(&event.event_type.as_str()))
+                .take(limit as usize)
+                .cloned(),
+        )
+    }
+
+    async fn latest_position(&self) -> Result<u64, AppError> {
+        Ok(self.inner.read().await.log.len() as u64)
+    }
+}
@@ -0,0 +1,150 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+use crate::error::AppError;
+
+/// Failed login attempts counted against one subject, such as a user or a client IP.
+#[derive(Debug, Clone, Default, PartialEq, sqlx::FromRow)]
+pub struct LoginAttempts {
+    /// Failures since `window_started_at`.
+    pub failed_count: u32,
+    pub window_started_at: Option<DateTime<Utc>>,
+    /// How many times the subject has been locked out, used to lengthen each lockout.
+    pub lockouts: u32,
+    /// End of the most recent lockout.
+    pub blocked_until: Option<DateTime<Utc>>,
+}
+
+/// Trait for counting failed login attempts.
+#[async_trait]
+pub trait LoginAttemptStore: Send + Sync {
+    /// The attempts recorded for `subject`, all zero if there are none.
+    async fn load_attempts(&self, subject: &str) -> Result<LoginAttempts, AppError>;
+
+    /// Counts a failure at `now`. Failures before `window_start` are forgotten and the
+    /// count starts again from this one.
+    async fn record_failure(&self, subject: &str, now: DateTime<Utc>, window_start: DateTime<Utc>) -> Result<LoginAttempts, AppError>;
+
+    /// Records the `lockouts`-th lockout lasting until `until` and clears the failure count.
+    async fn record_lockout(&self, subject: &str, lockouts: u32, until: DateTime<Utc>) -> Result<(), AppError>;
+
+    /// Forgets everything recorded for `subject`.
+    async fn reset(&self, subject: &str) -> Result<(), AppError>;
+}
+
+pub struct SqlxLoginAttemptStore {
+    pool: MySqlPool,
+}
+
+impl SqlxLoginAttemptStore {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl LoginAttemptStore for SqlxLoginAttemptStore {
+    async fn load_attempts(&self, subject: &str) -> Result<LoginAttempts, AppError> {
+        let attempts = sqlx::query_as::<_, LoginAttempts>(
+            "SELECT failed_count, window_started_at, lockouts, blocked_until FROM login_attempts WHERE subject = ?"
+        )
+        .bind(subject)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(attempts.unwrap_or_default())
+    }
+
+    async fn record_failure(&self, subject: &str, now: DateTime<Utc>, window_start: DateTime<Utc>) -> Result<LoginAttempts, AppError> {
+        // The count is reset and incremented in one statement so concurrent failures are all counted.
+        // MySQL applies the assignments in order, so window_started_at must be updated last.
+        sqlx::query(
+            "INSERT INTO login_attempts (subject, failed_count, window_started_at) VALUES (?, 1, ?) \
+             ON DUPLICATE KEY UPDATE \
+             failed_count = IF(window_started_at IS NULL OR window_started_at < ?, 1, failed_count + 1), \
+             window_started_at = IF(window_started_at IS NULL OR window_started_at < ?, VALUES(window_started_at), window_started_at)"
+        )
+        .bind(subject)
+        .bind(now)
+        .bind(window_start)
+        .bind(window_start)
+        .execute(&self.pool)
+        .await?;
+
+        self.load_attempts(subject).await
+    }
+
+    async fn record_lockout(&self, subject: &str, lockouts: u32, until: DateTime<Utc>) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE login_attempts SET failed_count = 0, window_started_at = NULL, lockouts = ?, blocked_until = ? \
+             WHERE subject = ?"
+        )
+        .bind(lockouts)
+        .bind(until)
+        .bind(subject)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn reset(&self, subject: &str) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM login_attempts WHERE subject = ?")
+            .bind(subject)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// A `LoginAttemptStore` kept in memory, for tests and local development.
+#[derive(Default)]
+pub struct InMemoryLoginAttemptStore {
+    attempts: RwLock<HashMap<String, LoginAttempts>>,
+}
+
+impl InMemoryLoginAttemptStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl LoginAttemptStore for InMemoryLoginAttemptStore {
+    async fn load_attempts(&self, subject: &str) -> Result<LoginAttempts, AppError> {
+        Ok(self.attempts.read().await.get(subject).cloned().unwrap_or_default())
+    }
+
+    async fn record_failure(&self, subject: &str, now: DateTime<Utc>, window_start: DateTime<Utc>) -> Result<LoginAttempts, AppError> {
+        let mut attempts = self.attempts.write().await;
+        let entry = attempts.entry(subject.to_string()).or_default();
+
+        if entry.window_started_at.is_none_or(|started_at| started_at < window_start) {
+            entry.failed_count = 1;
+            entry.window_started_at = Some(now);
+        } else {
+            entry.failed_count += 1;
+        }
+
+        Ok(entry.clone())
+    }
+
+    async fn record_lockout(&self, subject: &str, lockouts: u32, until: DateTime<Utc>) -> Result<(), AppError> {
+        let mut attempts = self.attempts.write().await;
+        let entry = attempts.entry(subject.to_string()).or_default();
+        entry.failed_count = 0;
+        entry.window_started_at = None;
+        entry.lockouts = lockouts;
+        entry.blocked_until = Some(until);
+        Ok(())
+    }
+
+    async fn reset(&self, subject: &str) -> Result<(), AppError> {
+        self.attempts.write().await.remove(subject);
+        Ok(())
+    }
+}
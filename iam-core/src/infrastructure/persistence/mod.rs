@@ -1,6 +1,27 @@
+pub mod api_permissions;
+pub mod effective_permissions;
 pub mod event_store;
+pub mod in_memory_event_store;
+pub mod login_attempts;
+pub mod projection;
 pub mod projectors;
-// pub mod snapshot_store; // Will be added later
+pub mod refresh_tokens;
+pub mod repository;
+pub mod snapshot_store;
+pub mod tenants;
+pub mod token_revocations;
+pub mod upcasting;
 
+pub use api_permissions::*;
+pub use effective_permissions::*;
 pub use event_store::*;
-pub use projectors::*;
\ No newline at end of file
+pub use in_memory_event_store::*;
+pub use login_attempts::*;
+pub use projection::*;
+pub use projectors::*;
+pub use refresh_tokens::*;
+pub use repository::*;
+pub use snapshot_store::*;
+pub use tenants::*;
+pub use token_revocations::*;
+pub use upcasting::*;
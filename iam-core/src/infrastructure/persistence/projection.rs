@@ -0,0 +1,615 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+use crate::error::AppError;
+use crate::infrastructure::persistence::event_store::{EventStore, StoredEvent};
+
+/// Builds a read model from the event stream.
+///
+/// Events are delivered in global position order, at least once: after a crash the
+/// events since the last persisted checkpoint are delivered again, so handling an
+/// event a second time must leave the read model unchanged. An event whose position
+/// was skipped because it committed too late is delivered when it shows up, after
+/// events with higher positions.
+#[async_trait]
+pub trait Projector: Send + Sync {
+    /// Unique name of the projection, used as the key of its checkpoint.
+    fn name(&self) -> &str;
+
+    async fn handle_event(&self, event: &StoredEvent) -> anyhow::Result<()>;
+
+    /// Deletes everything the projector has written, so the events can be replayed from the start.
+    async fn reset(&self) -> anyhow::Result<()>;
+
+    /// Creates empty shadow copies of the projector's tables and returns a projector that
+    /// writes to them, or `None` if the projection can only be rebuilt in place.
+    async fn create_shadow(&self) -> anyhow::Result<Option<Arc<dyn Projector>>> {
+        Ok(None)
+    }
+
+    /// Atomically replaces the live tables with the shadow copies made by `create_shadow`.
+    async fn promote_shadow(&self) -> anyhow::Result<()> {
+        anyhow::bail!("Projection {} has no shadow tables", self.name())
+    }
+}
+
+/// Trait for persisting how far each projector has read the event stream.
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// The position of the last event the projector has handled, 0 if it has not started.
+    async fn load_checkpoint(&self, projector: &str) -> Result<u64, AppError>;
+
+    async fn save_checkpoint(&self, projector: &str, position: u64) -> Result<(), AppError>;
+}
+
+use sqlx::MySqlPool;
+
+pub struct SqlxCheckpointStore {
+    pool: MySqlPool,
+}
+
+impl SqlxCheckpointStore {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for SqlxCheckpointStore {
+    async fn load_checkpoint(&self, projector: &str) -> Result<u64, AppError> {
+        let position: Option<u64> = sqlx::query_scalar("SELECT position FROM projection_checkpoints WHERE projector = ?")
+            .bind(projector)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(position.unwrap_or(0))
+    }
+
+    async fn save_checkpoint(&self, projector: &str, position: u64) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO projection_checkpoints (projector, position) VALUES (?, ?) \
+             ON DUPLICATE KEY UPDATE position = VALUES(position)"
+        )
+        .bind(projector)
+        .bind(position)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// A `CheckpointStore` kept in memory, for tests and local development.
+#[derive(Default)]
+pub struct InMemoryCheckpointStore {
+    checkpoints: RwLock<HashMap<String, u64>>,
+}
+
+impl InMemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn load_checkpoint(&self, projector: &str) -> Result<u64, AppError> {
+        Ok(self.checkpoints.read().await.get(projector).copied().unwrap_or(0))
+    }
+
+    async fn save_checkpoint(&self, projector: &str, position: u64) -> Result<(), AppError> {
+        self.checkpoints.write().await.insert(projector.to_string(), position);
+        Ok(())
+    }
+}
+
+/// Reads the event stream in position order on behalf of projectors.
+///
+/// Positions are assigned when an event is inserted, not when it commits, so a missing
+/// position may still show up shortly afterwards. The feed waits up to `gap_timeout`
+/// for it before moving on, then keeps looking for the skipped position for another
+/// `skipped_retention` and delivers the event late if it commits in that time. Positions
+/// burnt by rolled-back transactions never appear and are given up on with an error.
+///
+/// Skipped positions are only tracked in memory; one still missing when the process
+/// stops is not looked for again.
+#[derive(Clone)]
+struct EventFeed {
+    event_store: Arc<dyn EventStore>,
+    batch_size: u64,
+    gap_timeout: Duration,
+    skipped_retention: Duration,
+    /// The first missing position each reader is waiting for, and since when.
+    gaps: Arc<Mutex<HashMap<String, (u64, Instant)>>>,
+    /// Positions each reader moved past without an event, and since when.
+    skipped: Arc<Mutex<HashMap<String, BTreeMap<u64, Instant>>>>,
+}
+
+struct Batch {
+    handled: usize,
+    /// No further events are available right now.
+    caught_up: bool,
+}
+
+impl EventFeed {
+    /// Feeds the projector one batch of events after `checkpoint`, advancing it as they are
+    /// handled. `reader` identifies the checkpoint for gap tracking.
+    async fn next_batch(&self, projector: &dyn Projector, reader: &str, checkpoint: &mut u64) -> Result<Batch, AppError> {
+        let mut handled = self.recover_skipped(projector, reader).await?;
+        let events = self.event_store.read_all(*checkpoint, self.batch_size).await?;
+
+        for event in &events {
+            if event.position != *checkpoint + 1 && !self.gap_expired(reader, *checkpoint + 1, event.position) {
+                return Ok(Batch { handled, caught_up: true });
+            }
+
+            projector.handle_event(event).await.map_err(|e| {
+                AppError::InternalError(format!("Failed to handle event at position {}: {}", event.position, e))
+            })?;
+            *checkpoint = event.position;
+            handled += 1;
+        }
+
+        Ok(Batch {
+            handled,
+            caught_up: (events.len() as u64) < self.batch_size,
+        })
+    }
+
+    /// Whether the reader has waited long enough for `expected` to commit and may
+    /// continue with `next`.
+    fn gap_expired(&self, reader: &str, expected: u64, next: u64) -> bool {
+        let mut gaps = self.gaps.lock().unwrap();
+        match gaps.get(reader) {
+            Some(&(position, since)) if position == expected => {
+                if since.elapsed() < self.gap_timeout {
+                    return false;
+                }
+                gaps.remove(reader);
+                tracing::warn!(
+                    "Projection {} skipping missing event positions {}..{}", reader, expected, next
+                );
+                let now = Instant::now();
+                self.skipped
+                    .lock()
+                    .unwrap()
+                    .entry(reader.to_string())
+                    .or_default()
+                    .extend((expected..next).map(|position| (position, now)));
+                true
+            }
+            _ => {
+                gaps.insert(reader.to_string(), (expected, Instant::now()));
+                false
+            }
+        }
+    }
+
+    /// Feeds the projector the events that committed at positions the reader skipped, and
+    /// gives up on positions that stayed empty for longer than `skipped_retention`.
+    async fn recover_skipped(&self, projector: &dyn Projector, reader: &str) -> Result<usize, AppError> {
+        let (first, last) = {
+            let mut skipped = self.skipped.lock().unwrap();
+            let Some(positions) = skipped.get_mut(reader) else {
+                return Ok(0);
+            };
+            positions.retain(|&position, since| {
+                let expired = since.elapsed() >= self.skipped_retention;
+                if expired {
+                    tracing::error!(
+                        "Projection {} gave up on event position {}; an event committed there later is never projected",
+                        reader, position
+                    );
+                }
+                !expired
+            });
+            match (positions.first_key_value(), positions.last_key_value()) {
+                (Some((&first, _)), Some((&last, _))) => (first, last),
+                _ => {
+                    skipped.remove(reader);
+                    return Ok(0);
+                }
+            }
+        };
+
+        let events = self.event_store.read_all(first - 1, last - first + 1).await?;
+        let mut handled = 0;
+        for event in events.iter().filter(|event| event.position <= last) {
+            if !self.skipped.lock().unwrap().get(reader).is_some_and(|positions| positions.contains_key(&event.position)) {
+                continue;
+            }
+
+            projector.handle_event(event).await.map_err(|e| {
+                AppError::InternalError(format!("Failed to handle event at position {}: {}", event.position, e))
+            })?;
+            if let Some(positions) = self.skipped.lock().unwrap().get_mut(reader) {
+                positions.remove(&event.position);
+            }
+            tracing::warn!("Projection {} handled event at skipped position {} late", reader, event.position);
+            handled += 1;
+        }
+        Ok(handled)
+    }
+
+    /// Replaces the positions skipped by `to` with those skipped by `from`, whose checkpoint `to` takes over.
+    fn hand_over_skipped(&self, from: &str, to: &str) {
+        let mut skipped = self.skipped.lock().unwrap();
+        match skipped.remove(from) {
+            Some(positions) => skipped.insert(to.to_string(), positions),
+            None => skipped.remove(to),
+        };
+    }
+
+    fn forget_skipped(&self, reader: &str) {
+        self.skipped.lock().unwrap().remove(reader);
+    }
+}
+
+/// A registered projector and the lock that keeps the runner out while it is rebuilt.
+#[derive(Clone)]
+struct Projection {
+    projector: Arc<dyn Projector>,
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+/// Tails the event store and feeds every registered projector from its own checkpoint.
+pub struct ProjectionRunner {
+    feed: EventFeed,
+    checkpoints: Arc<dyn CheckpointStore>,
+    projections: Vec<Projection>,
+    poll_interval: Duration,
+}
+
+impl ProjectionRunner {
+    pub fn new(event_store: Arc<dyn EventStore>, checkpoints: Arc<dyn CheckpointStore>) -> Self {
+        Self {
+            feed: EventFeed {
+                event_store,
+                batch_size: 100,
+                gap_timeout: Duration::from_secs(5),
+                skipped_retention: Duration::from_secs(600),
+                gaps: Arc::new(Mutex::new(HashMap::new())),
+                skipped: Arc::new(Mutex::new(HashMap::new())),
+            },
+            checkpoints,
+            projections: Vec::new(),
+            poll_interval: Duration::from_millis(500),
+        }
+    }
+
+    pub fn with_projector(mut self, projector: Arc<dyn Projector>) -> Self {
+        self.projections.push(Projection {
+            projector,
+            lock: Arc::new(tokio::sync::Mutex::new(())),
+        });
+        self
+    }
+
+    /// Number of events read from the store per query.
+    pub fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.feed.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// How long to wait before polling again once every projector has caught up.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// How long to wait for a missing position before skipping it.
+    pub fn with_gap_timeout(mut self, gap_timeout: Duration) -> Self {
+        self.feed.gap_timeout = gap_timeout;
+        self
+    }
+
+    /// How long to keep looking for an event at a skipped position before giving up on it.
+    pub fn with_skipped_retention(mut self, skipped_retention: Duration) -> Self {
+        self.feed.skipped_retention = skipped_retention;
+        self
+    }
+
+    /// A rebuilder for the registered projections that coordinates with this runner.
+    pub fn rebuilder(&self) -> ProjectionRebuilder {
+        ProjectionRebuilder {
+            feed: self.feed.clone(),
+            checkpoints: self.checkpoints.clone(),
+            projections: self.projections.clone(),
+            statuses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Runs the projectors in a background task until the process exits.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    pub async fn run(self) {
+        tracing::info!("Projection runner started with {} projector(s)", self.projections.len());
+        loop {
+            if self.run_once().await == 0 {
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        }
+    }
+
+    /// Lets every projector catch up with the event store once and returns how many
+    /// events were handled. A failing projector is logged and retried on the next run
+    /// from its last checkpoint, without holding back the others.
+    pub async fn run_once(&self) -> usize {
+        let mut handled = 0;
+        for projection in &self.projections {
+            match self.catch_up(projection).await {
+                Ok(count) => handled += count,
+                Err(e) => tracing::error!("Projection {} failed: {}", projection.projector.name(), e),
+            }
+        }
+        handled
+    }
+
+    async fn catch_up(&self, projection: &Projection) -> Result<usize, AppError> {
+        // A rebuild holds the lock while it rewrites the projection; it resumes on a later run.
+        let Ok(_guard) = projection.lock.try_lock() else {
+            return Ok(0);
+        };
+
+        let projector = projection.projector.as_ref();
+        let name = projector.name();
+        let mut saved = self.checkpoints.load_checkpoint(name).await?;
+        let mut checkpoint = saved;
+        let mut handled = 0;
+
+        let result = loop {
+            let batch = match self.feed.next_batch(projector, name, &mut checkpoint).await {
+                Ok(batch) => batch,
+                Err(e) => break Err(e),
+            };
+            handled += batch.handled;
+            if batch.caught_up {
+                break Ok(handled);
+            }
+
+            // Persist progress between batches so a long catch-up survives a restart.
+            if let Err(e) = self.checkpoints.save_checkpoint(name, checkpoint).await {
+                break Err(e);
+            }
+            saved = checkpoint;
+        };
+
+        // Keep the progress made before a failure so the next run resumes right after it.
+        if checkpoint != saved {
+            self.checkpoints.save_checkpoint(name, checkpoint).await?;
+        }
+        result
+    }
+}
+
+/// How a projection is rebuilt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RebuildMode {
+    /// Empty the live tables and replay into them; reads see a partial projection meanwhile.
+    InPlace,
+    /// Replay into shadow tables and swap them in at the end; reads keep working throughout.
+    Shadow,
+}
+
+impl RebuildMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RebuildMode::InPlace => "in_place",
+            RebuildMode::Shadow => "shadow",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RebuildState {
+    Running,
+    Completed,
+    Failed,
+}
+
+impl RebuildState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RebuildState::Running => "running",
+            RebuildState::Completed => "completed",
+            RebuildState::Failed => "failed",
+        }
+    }
+}
+
+/// Progress of the latest rebuild of a projection.
+#[derive(Debug, Clone, Serialize)]
+pub struct RebuildStatus {
+    pub projection: String,
+    pub mode: RebuildMode,
+    pub state: RebuildState,
+    /// Position of the last event replayed so far.
+    pub position: u64,
+    /// Position of the newest event when the rebuild started.
+    pub target_position: u64,
+    pub events_replayed: u64,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Rebuilds projections from the full event history.
+///
+/// The rebuilder shares a lock per projection with the `ProjectionRunner` it was created
+/// from, so the runner leaves a projection alone while it is being rewritten.
+pub struct ProjectionRebuilder {
+    feed: EventFeed,
+    checkpoints: Arc<dyn CheckpointStore>,
+    projections: Vec<Projection>,
+    statuses: Mutex<HashMap<String, RebuildStatus>>,
+}
+
+impl ProjectionRebuilder {
+    /// Names of the projections that can be rebuilt.
+    pub fn projections(&self) -> Vec<String> {
+        self.projections.iter().map(|projection| projection.projector.name().to_string()).collect()
+    }
+
+    /// The progress of the latest rebuild of `name`, if it was rebuilt since startup.
+    pub fn status(&self, name: &str) -> Option<RebuildStatus> {
+        self.statuses.lock().unwrap().get(name).cloned()
+    }
+
+    /// Starts rebuilding `name` in a background task and returns its initial status.
+    pub async fn start(self: Arc<Self>, name: &str, mode: RebuildMode) -> Result<RebuildStatus, AppError> {
+        let (projection, status) = self.begin(name, mode).await?;
+
+        tokio::spawn(async move {
+            let result = self.replay(&projection, mode).await;
+            self.finish(projection.projector.name(), &result);
+        });
+
+        Ok(status)
+    }
+
+    /// Rebuilds `name` and waits for the rebuild to finish.
+    pub async fn rebuild(&self, name: &str, mode: RebuildMode) -> Result<RebuildStatus, AppError> {
+        let (projection, _) = self.begin(name, mode).await?;
+
+        let result = self.replay(&projection, mode).await;
+        let status = self.finish(name, &result);
+        result.map(|()| status)
+    }
+
+    async fn begin(&self, name: &str, mode: RebuildMode) -> Result<(Projection, RebuildStatus), AppError> {
+        let projection = self
+            .projections
+            .iter()
+            .find(|projection| projection.projector.name() == name)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("Projection {} not found", name)))?;
+        let target_position = self.feed.event_store.latest_position().await?;
+
+        let mut statuses = self.statuses.lock().unwrap();
+        if statuses.get(name).is_some_and(|status| status.state == RebuildState::Running) {
+            return Err(AppError::DomainError(format!("Projection {} is already being rebuilt", name)));
+        }
+
+        let status = RebuildStatus {
+            projection: name.to_string(),
+            mode,
+            state: RebuildState::Running,
+            position: 0,
+            target_position,
+            events_replayed: 0,
+            error: None,
+            started_at: Utc::now(),
+            finished_at: None,
+        };
+        statuses.insert(name.to_string(), status.clone());
+        tracing::info!("Rebuilding projection {} ({:?}) up to position {}", name, mode, target_position);
+
+        Ok((projection, status))
+    }
+
+    fn finish(&self, name: &str, result: &Result<(), AppError>) -> RebuildStatus {
+        let mut statuses = self.statuses.lock().unwrap();
+        let status = statuses.get_mut(name).expect("rebuild status is set when the rebuild begins");
+
+        status.finished_at = Some(Utc::now());
+        match result {
+            Ok(()) => {
+                status.state = RebuildState::Completed;
+                tracing::info!("Rebuilt projection {} from {} events", name, status.events_replayed);
+            }
+            Err(e) => {
+                status.state = RebuildState::Failed;
+                status.error = Some(e.to_string());
+                tracing::error!("Rebuilding projection {} failed: {}", name, e);
+            }
+        }
+        status.clone()
+    }
+
+    async fn replay(&self, projection: &Projection, mode: RebuildMode) -> Result<(), AppError> {
+        let projector = projection.projector.as_ref();
+        let name = projector.name();
+        let internal = |e: anyhow::Error| AppError::InternalError(format!("Projection {}: {}", name, e));
+        let reader = rebuild_reader(name);
+        self.feed.forget_skipped(&reader);
+
+        match mode {
+            RebuildMode::InPlace => {
+                let _guard = projection.lock.lock().await;
+
+                projector.reset().await.map_err(internal)?;
+                self.checkpoints.save_checkpoint(name, 0).await?;
+
+                let mut checkpoint = 0;
+                self.replay_into(projector, name, &mut checkpoint, true).await?;
+                self.checkpoints.save_checkpoint(name, checkpoint).await?;
+                self.feed.hand_over_skipped(&reader, name);
+            }
+            RebuildMode::Shadow => {
+                let shadow = projector
+                    .create_shadow()
+                    .await
+                    .map_err(internal)?
+                    .ok_or_else(|| AppError::DomainError(format!("Projection {} can only be rebuilt in place", name)))?;
+
+                // Replay the bulk of the history while the runner keeps the live tables current,
+                // then hold it off to replay what was appended meanwhile and swap the tables.
+                let mut checkpoint = 0;
+                self.replay_into(shadow.as_ref(), name, &mut checkpoint, false).await?;
+
+                let _guard = projection.lock.lock().await;
+                self.replay_into(shadow.as_ref(), name, &mut checkpoint, false).await?;
+                projector.promote_shadow().await.map_err(internal)?;
+                self.checkpoints.save_checkpoint(name, checkpoint).await?;
+                self.feed.hand_over_skipped(&reader, name);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replays events after `checkpoint` into `projector` until it has caught up, recording
+    /// progress under `name` and, if `persist` is set, in the checkpoint store.
+    async fn replay_into(&self, projector: &dyn Projector, name: &str, checkpoint: &mut u64, persist: bool) -> Result<(), AppError> {
+        let reader = rebuild_reader(name);
+        let mut last_report = Instant::now();
+
+        loop {
+            let batch = self.feed.next_batch(projector, &reader, checkpoint).await?;
+            if persist {
+                self.checkpoints.save_checkpoint(name, *checkpoint).await?;
+            }
+
+            if let Some(status) = self.statuses.lock().unwrap().get_mut(name) {
+                status.position = *checkpoint;
+                status.events_replayed += batch.handled as u64;
+
+                if last_report.elapsed() >= Duration::from_secs(5) {
+                    tracing::info!(
+                        "Rebuilding projection {}: position {} of {}", name, status.position, status.target_position
+                    );
+                    last_report = Instant::now();
+                }
+            }
+
+            if batch.caught_up {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// The feed reader a rebuild of `name` replays with, kept apart from the runner's.
+fn rebuild_reader(name: &str) -> String {
+    format!("{}:rebuild", name)
+}
@@ -1,19 +1,78 @@
 use crate::application::dtos as user_view;
+use crate::application::dtos::role_view;
+use crate::application::dtos::tenant_view;
+use crate::application::dtos::organization_view::{self, path_segment};
+use crate::application::dtos::organization_member_view;
+use crate::application::dtos::{permission_view, role_permission_view};
+use crate::domain::identity_access::aggregates::tenant::TenantStatus;
 use crate::domain::identity_access::events::IdentityAccessEvent;
 use crate::infrastructure::persistence::event_store::StoredEvent;
+use crate::infrastructure::persistence::projection::Projector;
 use anyhow::Result;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, Set, EntityTrait};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::{Alias, Expr, OnConflict, Query, SimpleExpr};
+use sea_orm::{ConnectionTrait, DatabaseConnection, Statement, TransactionTrait};
+use std::sync::Arc;
+
+/// Suffix of the tables a projection is rebuilt into before they are swapped in.
+const SHADOW_SUFFIX: &str = "_shadow";
+/// Suffix the replaced live tables carry until they are dropped.
+const RETIRED_SUFFIX: &str = "_retired";
+
+/// Runs DDL against read model tables; table names are internal constants, never user input.
+async fn execute_ddl(db: &DatabaseConnection, sql: String) -> Result<()> {
+    db.execute(Statement::from_string(db.get_database_backend(), sql)).await?;
+    Ok(())
+}
+
+/// Creates an empty `<table>_shadow` with the same structure as `table`.
+async fn create_shadow_table(db: &DatabaseConnection, table: &str) -> Result<()> {
+    execute_ddl(db, format!("DROP TABLE IF EXISTS `{table}{SHADOW_SUFFIX}`")).await?;
+    execute_ddl(db, format!("CREATE TABLE `{table}{SHADOW_SUFFIX}` LIKE `{table}`")).await
+}
+
+/// Swaps `<table>_shadow` in for `table` in a single atomic rename.
+async fn promote_shadow_table(db: &DatabaseConnection, table: &str) -> Result<()> {
+    execute_ddl(db, format!("DROP TABLE IF EXISTS `{table}{RETIRED_SUFFIX}`")).await?;
+    execute_ddl(db, format!(
+        "RENAME TABLE `{table}` TO `{table}{RETIRED_SUFFIX}`, `{table}{SHADOW_SUFFIX}` TO `{table}`"
+    )).await?;
+    execute_ddl(db, format!("DROP TABLE `{table}{RETIRED_SUFFIX}`")).await
+}
 
 pub struct UserProjector {
     db: DatabaseConnection,
+    table: String,
 }
 
 impl UserProjector {
     pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+        Self { db, table: "users_view".to_string() }
     }
 
-    pub async fn handle_event(&self, event: &StoredEvent) -> Result<()> {
+    async fn update_user(&self, user_id: uuid::Uuid, values: Vec<(user_view::Column, SimpleExpr)>) -> Result<()> {
+        let statement = Query::update()
+            .table(Alias::new(&self.table))
+            .values(values)
+            .and_where(Expr::col(user_view::Column::Id).eq(user_id))
+            .to_owned();
+
+        let result = self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+        if result.rows_affected() == 0 {
+            return Err(anyhow::anyhow!("User not found"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Projector for UserProjector {
+    fn name(&self) -> &str {
+        "users"
+    }
+
+    async fn handle_event(&self, event: &StoredEvent) -> Result<()> {
         match event.event_type.as_str() {
             "UserRegistered" => {
                 let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
@@ -22,18 +81,38 @@ impl UserProjector {
                     _ => return Err(anyhow::anyhow!("Invalid event type")),
                 };
 
-                let new_user = user_view::ActiveModel {
-                    id: Set(user_registered.user_id.into()),
-                    tenant_id: Set(user_registered.tenant_id.into()),
-                    username: Set(user_registered.username),
-                    email: Set(user_registered.email),
-                    password_hash: Set(user_registered.password_hash),
-                    status: Set("active".to_string()),
-                    created_at: Set(event.created_at.into()),
-                    updated_at: Set(event.created_at.into()),
-                };
+                // A redelivered registration must not fail on the existing row, nor reset
+                // changes made by later events, which are redelivered after it anyway.
+                let statement = Query::insert()
+                    .into_table(Alias::new(&self.table))
+                    .columns([
+                        user_view::Column::Id,
+                        user_view::Column::TenantId,
+                        user_view::Column::Username,
+                        user_view::Column::Email,
+                        user_view::Column::PasswordHash,
+                        user_view::Column::Status,
+                        user_view::Column::CreatedAt,
+                        user_view::Column::UpdatedAt,
+                    ])
+                    .values_panic([
+                        user_registered.user_id.into(),
+                        user_registered.tenant_id.into(),
+                        user_registered.username.into(),
+                        user_registered.email.into(),
+                        user_registered.password_hash.into(),
+                        "active".into(),
+                        event.created_at.into(),
+                        event.created_at.into(),
+                    ])
+                    .on_conflict(
+                        OnConflict::column(user_view::Column::Id)
+                            .update_column(user_view::Column::Id)
+                            .to_owned(),
+                    )
+                    .to_owned();
 
-                new_user.insert(&self.db).await?;
+                self.db.execute(self.db.get_database_backend().build(&statement)).await?;
             }
             "UserUpdated" => {
                 let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
@@ -42,21 +121,15 @@ impl UserProjector {
                     _ => return Err(anyhow::anyhow!("Invalid event type")),
                 };
 
-                let mut user: user_view::ActiveModel = user_view::Entity::find_by_id(user_updated.user_id)
-                    .one(&self.db)
-                    .await?
-                    .ok_or_else(|| anyhow::anyhow!("User not found"))?
-                    .into();
-
+                let mut values = vec![(user_view::Column::UpdatedAt, event.created_at.into())];
                 if let Some(username) = user_updated.username {
-                    user.username = Set(username);
+                    values.push((user_view::Column::Username, username.into()));
                 }
                 if let Some(email) = user_updated.email {
-                    user.email = Set(email);
+                    values.push((user_view::Column::Email, email.into()));
                 }
-                user.updated_at = Set(event.created_at.into());
 
-                user.update(&self.db).await?;
+                self.update_user(user_updated.user_id, values).await?;
             }
             "UserDeactivated" => {
                 let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
@@ -65,20 +138,1156 @@ impl UserProjector {
                     _ => return Err(anyhow::anyhow!("Invalid event type")),
                 };
 
-                let mut user: user_view::ActiveModel = user_view::Entity::find_by_id(user_deactivated.user_id)
-                    .one(&self.db)
-                    .await?
-                    .ok_or_else(|| anyhow::anyhow!("User not found"))?
-                    .into();
+                self.update_user(user_deactivated.user_id, vec![
+                    (user_view::Column::Status, "inactive".into()),
+                    (user_view::Column::LockedUntil, Option::<DateTime<Utc>>::None.into()),
+                    (user_view::Column::UpdatedAt, event.created_at.into()),
+                ]).await?;
+            }
+            "UserReactivated" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let user_reactivated = match payload {
+                    IdentityAccessEvent::UserReactivated(user_reactivated) => user_reactivated,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
 
-                user.status = Set("inactive".to_string());
-                user.updated_at = Set(event.created_at.into());
+                self.update_user(user_reactivated.user_id, vec![
+                    (user_view::Column::Status, "active".into()),
+                    (user_view::Column::UpdatedAt, event.created_at.into()),
+                ]).await?;
+            }
+            "UserLocked" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let user_locked = match payload {
+                    IdentityAccessEvent::UserLocked(user_locked) => user_locked,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.update_user(user_locked.user_id, vec![
+                    (user_view::Column::Status, "locked".into()),
+                    (user_view::Column::LockedUntil, user_locked.locked_until.into()),
+                    (user_view::Column::UpdatedAt, event.created_at.into()),
+                ]).await?;
+            }
+            "UserUnlocked" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let user_unlocked = match payload {
+                    IdentityAccessEvent::UserUnlocked(user_unlocked) => user_unlocked,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
 
-                user.update(&self.db).await?;
+                self.update_user(user_unlocked.user_id, vec![
+                    (user_view::Column::Status, "active".into()),
+                    (user_view::Column::LockedUntil, Option::<DateTime<Utc>>::None.into()),
+                    (user_view::Column::UpdatedAt, event.created_at.into()),
+                ]).await?;
             }
             // Other event types can be handled here...
             _ => {}
         }
         Ok(())
     }
-}
\ No newline at end of file
+
+    async fn reset(&self) -> Result<()> {
+        execute_ddl(&self.db, format!("TRUNCATE TABLE `{}`", self.table)).await
+    }
+
+    async fn create_shadow(&self) -> Result<Option<Arc<dyn Projector>>> {
+        create_shadow_table(&self.db, &self.table).await?;
+
+        Ok(Some(Arc::new(UserProjector {
+            db: self.db.clone(),
+            table: format!("{}{}", self.table, SHADOW_SUFFIX),
+        })))
+    }
+
+    async fn promote_shadow(&self) -> Result<()> {
+        promote_shadow_table(&self.db, &self.table).await
+    }
+}
+
+pub struct RoleProjector {
+    db: DatabaseConnection,
+    table: String,
+}
+
+impl RoleProjector {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db, table: "roles_view".to_string() }
+    }
+
+    async fn update_role(&self, role_id: uuid::Uuid, values: Vec<(role_view::Column, SimpleExpr)>) -> Result<()> {
+        let statement = Query::update()
+            .table(Alias::new(&self.table))
+            .values(values)
+            .and_where(Expr::col(role_view::Column::Id).eq(role_id))
+            .to_owned();
+
+        let result = self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+        if result.rows_affected() == 0 {
+            return Err(anyhow::anyhow!("Role not found"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Projector for RoleProjector {
+    fn name(&self) -> &str {
+        "roles"
+    }
+
+    async fn handle_event(&self, event: &StoredEvent) -> Result<()> {
+        match event.event_type.as_str() {
+            "RoleCreated" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let role_created = match payload {
+                    IdentityAccessEvent::RoleCreated(role_created) => role_created,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                // Redelivered creations leave the existing row, and any later changes, untouched.
+                let statement = Query::insert()
+                    .into_table(Alias::new(&self.table))
+                    .columns([
+                        role_view::Column::Id,
+                        role_view::Column::TenantId,
+                        role_view::Column::Name,
+                        role_view::Column::Code,
+                        role_view::Column::Description,
+                        role_view::Column::Deleted,
+                        role_view::Column::CreatedAt,
+                        role_view::Column::UpdatedAt,
+                    ])
+                    .values_panic([
+                        role_created.role_id.into(),
+                        role_created.tenant_id.into(),
+                        role_created.name.into(),
+                        role_created.code.into(),
+                        role_created.description.into(),
+                        false.into(),
+                        event.created_at.into(),
+                        event.created_at.into(),
+                    ])
+                    .on_conflict(
+                        OnConflict::column(role_view::Column::Id)
+                            .update_column(role_view::Column::Id)
+                            .to_owned(),
+                    )
+                    .to_owned();
+
+                self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+            }
+            "RoleUpdated" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let role_updated = match payload {
+                    IdentityAccessEvent::RoleUpdated(role_updated) => role_updated,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                let mut values = vec![(role_view::Column::UpdatedAt, event.created_at.into())];
+                if let Some(name) = role_updated.name {
+                    values.push((role_view::Column::Name, name.into()));
+                }
+                if let Some(description) = role_updated.description {
+                    values.push((role_view::Column::Description, description.into()));
+                }
+
+                self.update_role(role_updated.role_id, values).await?;
+            }
+            "RoleDeleted" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let role_deleted = match payload {
+                    IdentityAccessEvent::RoleDeleted(role_deleted) => role_deleted,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                // Deleted roles stay in the view so queries can still report them.
+                self.update_role(role_deleted.role_id, vec![
+                    (role_view::Column::Deleted, true.into()),
+                    (role_view::Column::DeletedAt, event.created_at.into()),
+                    (role_view::Column::UpdatedAt, event.created_at.into()),
+                ]).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn reset(&self) -> Result<()> {
+        execute_ddl(&self.db, format!("TRUNCATE TABLE `{}`", self.table)).await
+    }
+
+    async fn create_shadow(&self) -> Result<Option<Arc<dyn Projector>>> {
+        create_shadow_table(&self.db, &self.table).await?;
+
+        Ok(Some(Arc::new(RoleProjector {
+            db: self.db.clone(),
+            table: format!("{}{}", self.table, SHADOW_SUFFIX),
+        })))
+    }
+
+    async fn promote_shadow(&self) -> Result<()> {
+        promote_shadow_table(&self.db, &self.table).await
+    }
+}
+
+/// Projects tenant events into the `tenants` table that requests are resolved against.
+pub struct TenantProjector {
+    db: DatabaseConnection,
+    table: String,
+}
+
+impl TenantProjector {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db, table: "tenants".to_string() }
+    }
+
+    async fn update_tenant(&self, tenant_id: uuid::Uuid, values: Vec<(tenant_view::Column, SimpleExpr)>) -> Result<()> {
+        let statement = Query::update()
+            .table(Alias::new(&self.table))
+            .values(values)
+            .and_where(Expr::col(tenant_view::Column::Id).eq(tenant_id))
+            .to_owned();
+
+        let result = self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+        if result.rows_affected() == 0 {
+            return Err(anyhow::anyhow!("Tenant not found"));
+        }
+        Ok(())
+    }
+
+    async fn set_status(&self, tenant_id: uuid::Uuid, status: TenantStatus, at: DateTime<Utc>) -> Result<()> {
+        self.update_tenant(tenant_id, vec![
+            (tenant_view::Column::Status, status.as_str().into()),
+            (tenant_view::Column::UpdatedAt, at.into()),
+        ]).await
+    }
+
+    /// Whether a tenant row matches `condition`.
+    async fn exists(&self, condition: SimpleExpr) -> Result<bool> {
+        let statement = Query::select()
+            .column(tenant_view::Column::Id)
+            .from(Alias::new(&self.table))
+            .and_where(condition)
+            .to_owned();
+
+        Ok(self.db.query_one(self.db.get_database_backend().build(&statement)).await?.is_some())
+    }
+}
+
+#[async_trait]
+impl Projector for TenantProjector {
+    fn name(&self) -> &str {
+        "tenants"
+    }
+
+    async fn handle_event(&self, event: &StoredEvent) -> Result<()> {
+        match event.event_type.as_str() {
+            "TenantCreated" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let tenant_created = match payload {
+                    IdentityAccessEvent::TenantCreated(tenant_created) => tenant_created,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                // Redelivered creations leave the existing row, and any later changes, untouched.
+                // Only a row with the same id is a redelivery: MySQL applies ON DUPLICATE KEY to the
+                // unique code too, which would silently drop a second tenant claiming the same code.
+                if self.exists(Expr::col(tenant_view::Column::Id).eq(tenant_created.tenant_id)).await? {
+                    return Ok(());
+                }
+                if self.exists(Expr::col(tenant_view::Column::Code).eq(tenant_created.code.as_str())).await? {
+                    return Err(anyhow::anyhow!(
+                        "Tenant {} cannot be projected: code {} is already used by another tenant",
+                        tenant_created.tenant_id,
+                        tenant_created.code
+                    ));
+                }
+
+                let statement = Query::insert()
+                    .into_table(Alias::new(&self.table))
+                    .columns([
+                        tenant_view::Column::Id,
+                        tenant_view::Column::Name,
+                        tenant_view::Column::Code,
+                        tenant_view::Column::Status,
+                        tenant_view::Column::CreatedAt,
+                        tenant_view::Column::UpdatedAt,
+                    ])
+                    .values_panic([
+                        tenant_created.tenant_id.into(),
+                        tenant_created.name.into(),
+                        tenant_created.code.into(),
+                        TenantStatus::Active.as_str().into(),
+                        event.created_at.into(),
+                        event.created_at.into(),
+                    ])
+                    .to_owned();
+
+                self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+            }
+            "TenantRenamed" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let tenant_renamed = match payload {
+                    IdentityAccessEvent::TenantRenamed(tenant_renamed) => tenant_renamed,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.update_tenant(tenant_renamed.tenant_id, vec![
+                    (tenant_view::Column::Name, tenant_renamed.name.into()),
+                    (tenant_view::Column::UpdatedAt, event.created_at.into()),
+                ]).await?;
+            }
+            "TenantSuspended" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let tenant_suspended = match payload {
+                    IdentityAccessEvent::TenantSuspended(tenant_suspended) => tenant_suspended,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.set_status(tenant_suspended.tenant_id, TenantStatus::Suspended, event.created_at).await?;
+            }
+            "TenantReactivated" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let tenant_reactivated = match payload {
+                    IdentityAccessEvent::TenantReactivated(tenant_reactivated) => tenant_reactivated,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.set_status(tenant_reactivated.tenant_id, TenantStatus::Active, event.created_at).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn reset(&self) -> Result<()> {
+        execute_ddl(&self.db, format!("TRUNCATE TABLE `{}`", self.table)).await
+    }
+
+    async fn create_shadow(&self) -> Result<Option<Arc<dyn Projector>>> {
+        create_shadow_table(&self.db, &self.table).await?;
+
+        Ok(Some(Arc::new(TenantProjector {
+            db: self.db.clone(),
+            table: format!("{}{}", self.table, SHADOW_SUFFIX),
+        })))
+    }
+
+    async fn promote_shadow(&self) -> Result<()> {
+        promote_shadow_table(&self.db, &self.table).await
+    }
+}
+
+/// Projects organization events into the `organizations` tree, keeping every row's level and
+/// materialized path in step with its position so subtrees can be read with a single prefix match.
+pub struct OrganizationProjector {
+    db: DatabaseConnection,
+    table: String,
+}
+
+impl OrganizationProjector {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db, table: "organizations".to_string() }
+    }
+
+    async fn update_organization(&self, organization_id: uuid::Uuid, values: Vec<(organization_view::Column, SimpleExpr)>) -> Result<()> {
+        let statement = Query::update()
+            .table(Alias::new(&self.table))
+            .values(values)
+            .and_where(Expr::col(organization_view::Column::Id).eq(organization_id))
+            .to_owned();
+
+        let result = self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+        if result.rows_affected() == 0 {
+            return Err(anyhow::anyhow!("Organization not found"));
+        }
+        Ok(())
+    }
+
+    /// Returns the level and path of a projected organization.
+    async fn position(&self, organization_id: uuid::Uuid) -> Result<(i32, String)> {
+        let statement = Query::select()
+            .columns([organization_view::Column::Level, organization_view::Column::Path])
+            .from(Alias::new(&self.table))
+            .and_where(Expr::col(organization_view::Column::Id).eq(organization_id))
+            .to_owned();
+
+        let row = self.db.query_one(self.db.get_database_backend().build(&statement)).await?
+            .ok_or_else(|| anyhow::anyhow!("Organization {} not found", organization_id))?;
+        Ok((row.try_get("", "level")?, row.try_get("", "path")?))
+    }
+
+    /// The level and path an organization takes under `parent_id`.
+    async fn position_under(&self, organization_id: uuid::Uuid, parent_id: Option<uuid::Uuid>) -> Result<(i32, String)> {
+        let (level, path) = match parent_id {
+            Some(parent_id) => {
+                let (level, path) = self.position(parent_id).await?;
+                (level + 1, path)
+            }
+            None => (0, "/".to_string()),
+        };
+        Ok((level, format!("{}{}", path, path_segment(organization_id))))
+    }
+}
+
+#[async_trait]
+impl Projector for OrganizationProjector {
+    fn name(&self) -> &str {
+        "organizations"
+    }
+
+    async fn handle_event(&self, event: &StoredEvent) -> Result<()> {
+        match event.event_type.as_str() {
+            "OrganizationCreated" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let organization_created = match payload {
+                    IdentityAccessEvent::OrganizationCreated(organization_created) => organization_created,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                let (level, path) = self
+                    .position_under(organization_created.organization_id, organization_created.parent_id)
+                    .await?;
+
+                // Redelivered creations leave the existing row, and any later changes, untouched.
+                let statement = Query::insert()
+                    .into_table(Alias::new(&self.table))
+                    .columns([
+                        organization_view::Column::Id,
+                        organization_view::Column::TenantId,
+                        organization_view::Column::ParentId,
+                        organization_view::Column::Name,
+                        organization_view::Column::Code,
+                        organization_view::Column::Level,
+                        organization_view::Column::Path,
+                        organization_view::Column::CreatedAt,
+                        organization_view::Column::UpdatedAt,
+                    ])
+                    .values_panic([
+                        organization_created.organization_id.into(),
+                        organization_created.tenant_id.into(),
+                        organization_created.parent_id.into(),
+                        organization_created.name.into(),
+                        organization_created.code.into(),
+                        level.into(),
+                        path.into(),
+                        event.created_at.into(),
+                        event.created_at.into(),
+                    ])
+                    .on_conflict(
+                        OnConflict::column(organization_view::Column::Id)
+                            .update_column(organization_view::Column::Id)
+                            .to_owned(),
+                    )
+                    .to_owned();
+
+                self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+            }
+            "OrganizationRenamed" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let organization_renamed = match payload {
+                    IdentityAccessEvent::OrganizationRenamed(organization_renamed) => organization_renamed,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.update_organization(organization_renamed.organization_id, vec![
+                    (organization_view::Column::Name, organization_renamed.name.into()),
+                    (organization_view::Column::UpdatedAt, event.created_at.into()),
+                ]).await?;
+            }
+            "OrganizationMoved" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let organization_moved = match payload {
+                    IdentityAccessEvent::OrganizationMoved(organization_moved) => organization_moved,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+                let organization_id = organization_moved.organization_id;
+
+                let (old_level, old_path) = self.position(organization_id).await?;
+                let (new_level, new_path) = self.position_under(organization_id, organization_moved.parent_id).await?;
+
+                self.update_organization(organization_id, vec![
+                    (organization_view::Column::ParentId, organization_moved.parent_id.into()),
+                    (organization_view::Column::UpdatedAt, event.created_at.into()),
+                ]).await?;
+
+                // Rewrites the path prefix and shifts the level of the organization and everything below it;
+                // a redelivered move finds the new path already in place and changes nothing.
+                let statement = Query::update()
+                    .table(Alias::new(&self.table))
+                    .value(
+                        organization_view::Column::Path,
+                        Expr::cust_with_values("CONCAT(?, SUBSTRING(`path`, ?))", [
+                            sea_orm::Value::from(new_path),
+                            sea_orm::Value::from(old_path.len() as i64 + 1),
+                        ]),
+                    )
+                    .value(
+                        organization_view::Column::Level,
+                        Expr::col(organization_view::Column::Level).add(new_level - old_level),
+                    )
+                    .and_where(Expr::col(organization_view::Column::Path).like(format!("{}%", old_path)))
+                    .to_owned();
+
+                self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+            }
+            "OrganizationDeleted" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let organization_deleted = match payload {
+                    IdentityAccessEvent::OrganizationDeleted(organization_deleted) => organization_deleted,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                let statement = Query::delete()
+                    .from_table(Alias::new(&self.table))
+                    .and_where(Expr::col(organization_view::Column::Id).eq(organization_deleted.organization_id))
+                    .to_owned();
+
+                self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn reset(&self) -> Result<()> {
+        execute_ddl(&self.db, format!("TRUNCATE TABLE `{}`", self.table)).await
+    }
+
+    async fn create_shadow(&self) -> Result<Option<Arc<dyn Projector>>> {
+        create_shadow_table(&self.db, &self.table).await?;
+
+        Ok(Some(Arc::new(OrganizationProjector {
+            db: self.db.clone(),
+            table: format!("{}{}", self.table, SHADOW_SUFFIX),
+        })))
+    }
+
+    async fn promote_shadow(&self) -> Result<()> {
+        promote_shadow_table(&self.db, &self.table).await
+    }
+}
+
+/// Projects organization memberships into `user_organizations`.
+pub struct OrganizationMemberProjector {
+    db: DatabaseConnection,
+    table: String,
+}
+
+impl OrganizationMemberProjector {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db, table: "user_organizations".to_string() }
+    }
+
+    async fn delete_members(&self, condition: SimpleExpr) -> Result<()> {
+        let statement = Query::delete()
+            .from_table(Alias::new(&self.table))
+            .and_where(condition)
+            .to_owned();
+
+        self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Projector for OrganizationMemberProjector {
+    fn name(&self) -> &str {
+        "organization_members"
+    }
+
+    async fn handle_event(&self, event: &StoredEvent) -> Result<()> {
+        match event.event_type.as_str() {
+            "UserOrganizationAssigned" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let assigned = match payload {
+                    IdentityAccessEvent::UserOrganizationAssigned(assigned) => assigned,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                let statement = Query::insert()
+                    .into_table(Alias::new(&self.table))
+                    .columns([
+                        organization_member_view::Column::UserId,
+                        organization_member_view::Column::OrganizationId,
+                        organization_member_view::Column::CreatedAt,
+                    ])
+                    .values_panic([
+                        assigned.user_id.into(),
+                        assigned.organization_id.into(),
+                        event.created_at.into(),
+                    ])
+                    .on_conflict(
+                        OnConflict::columns([
+                            organization_member_view::Column::UserId,
+                            organization_member_view::Column::OrganizationId,
+                        ])
+                        .update_column(organization_member_view::Column::UserId)
+                        .to_owned(),
+                    )
+                    .to_owned();
+
+                self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+            }
+            "UserOrganizationRemoved" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let removed = match payload {
+                    IdentityAccessEvent::UserOrganizationRemoved(removed) => removed,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.delete_members(
+                    Expr::col(organization_member_view::Column::UserId).eq(removed.user_id)
+                        .and(Expr::col(organization_member_view::Column::OrganizationId).eq(removed.organization_id)),
+                ).await?;
+            }
+            "OrganizationDeleted" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let organization_deleted = match payload {
+                    IdentityAccessEvent::OrganizationDeleted(organization_deleted) => organization_deleted,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                // Users keep the membership in their own stream, but a deleted organization has no members to list.
+                self.delete_members(
+                    Expr::col(organization_member_view::Column::OrganizationId).eq(organization_deleted.organization_id),
+                ).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn reset(&self) -> Result<()> {
+        execute_ddl(&self.db, format!("TRUNCATE TABLE `{}`", self.table)).await
+    }
+
+    async fn create_shadow(&self) -> Result<Option<Arc<dyn Projector>>> {
+        create_shadow_table(&self.db, &self.table).await?;
+
+        Ok(Some(Arc::new(OrganizationMemberProjector {
+            db: self.db.clone(),
+            table: format!("{}{}", self.table, SHADOW_SUFFIX),
+        })))
+    }
+
+    async fn promote_shadow(&self) -> Result<()> {
+        promote_shadow_table(&self.db, &self.table).await
+    }
+}
+
+/// Projects the permission catalog into `permissions_view`.
+pub struct PermissionProjector {
+    db: DatabaseConnection,
+    table: String,
+}
+
+impl PermissionProjector {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db, table: "permissions_view".to_string() }
+    }
+
+    async fn update_permission(&self, permission_id: uuid::Uuid, values: Vec<(permission_view::Column, SimpleExpr)>) -> Result<()> {
+        let statement = Query::update()
+            .table(Alias::new(&self.table))
+            .values(values)
+            .and_where(Expr::col(permission_view::Column::Id).eq(permission_id))
+            .to_owned();
+
+        let result = self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+        if result.rows_affected() == 0 {
+            return Err(anyhow::anyhow!("Permission not found"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Projector for PermissionProjector {
+    fn name(&self) -> &str {
+        "permissions"
+    }
+
+    async fn handle_event(&self, event: &StoredEvent) -> Result<()> {
+        match event.event_type.as_str() {
+            "PermissionCreated" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let permission_created = match payload {
+                    IdentityAccessEvent::PermissionCreated(permission_created) => permission_created,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                // Redelivered creations leave the existing row, and any later changes, untouched.
+                let statement = Query::insert()
+                    .into_table(Alias::new(&self.table))
+                    .columns([
+                        permission_view::Column::Id,
+                        permission_view::Column::ParentId,
+                        permission_view::Column::PermissionType,
+                        permission_view::Column::Name,
+                        permission_view::Column::Code,
+                        permission_view::Column::Route,
+                        permission_view::Column::Icon,
+                        permission_view::Column::Description,
+                        permission_view::Column::Deleted,
+                        permission_view::Column::CreatedAt,
+                        permission_view::Column::UpdatedAt,
+                    ])
+                    .values_panic([
+                        permission_created.permission_id.into(),
+                        permission_created.parent_id.into(),
+                        permission_created.permission_type.into(),
+                        permission_created.name.into(),
+                        permission_created.code.into(),
+                        permission_created.route.into(),
+                        permission_created.icon.into(),
+                        permission_created.description.into(),
+                        false.into(),
+                        event.created_at.into(),
+                        event.created_at.into(),
+                    ])
+                    .on_conflict(
+                        OnConflict::column(permission_view::Column::Id)
+                            .update_column(permission_view::Column::Id)
+                            .to_owned(),
+                    )
+                    .to_owned();
+
+                self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+            }
+            "PermissionUpdated" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let permission_updated = match payload {
+                    IdentityAccessEvent::PermissionUpdated(permission_updated) => permission_updated,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                let mut values = vec![(permission_view::Column::UpdatedAt, event.created_at.into())];
+                if let Some(name) = permission_updated.name {
+                    values.push((permission_view::Column::Name, name.into()));
+                }
+                if let Some(route) = permission_updated.route {
+                    values.push((permission_view::Column::Route, route.into()));
+                }
+                if let Some(icon) = permission_updated.icon {
+                    values.push((permission_view::Column::Icon, icon.into()));
+                }
+                if let Some(description) = permission_updated.description {
+                    values.push((permission_view::Column::Description, description.into()));
+                }
+
+                self.update_permission(permission_updated.permission_id, values).await?;
+            }
+            "PermissionDeleted" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let permission_deleted = match payload {
+                    IdentityAccessEvent::PermissionDeleted(permission_deleted) => permission_deleted,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                // Deleted permissions stay in the view so queries can still report them.
+                self.update_permission(permission_deleted.permission_id, vec![
+                    (permission_view::Column::Deleted, true.into()),
+                    (permission_view::Column::DeletedAt, event.created_at.into()),
+                    (permission_view::Column::UpdatedAt, event.created_at.into()),
+                ]).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn reset(&self) -> Result<()> {
+        execute_ddl(&self.db, format!("TRUNCATE TABLE `{}`", self.table)).await
+    }
+
+    async fn create_shadow(&self) -> Result<Option<Arc<dyn Projector>>> {
+        create_shadow_table(&self.db, &self.table).await?;
+
+        Ok(Some(Arc::new(PermissionProjector {
+            db: self.db.clone(),
+            table: format!("{}{}", self.table, SHADOW_SUFFIX),
+        })))
+    }
+
+    async fn promote_shadow(&self) -> Result<()> {
+        promote_shadow_table(&self.db, &self.table).await
+    }
+}
+
+/// Projects the permissions granted to roles into `role_permissions`.
+pub struct RolePermissionProjector {
+    db: DatabaseConnection,
+    table: String,
+}
+
+impl RolePermissionProjector {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db, table: "role_permissions".to_string() }
+    }
+
+    async fn delete_grants(&self, condition: SimpleExpr) -> Result<()> {
+        let statement = Query::delete()
+            .from_table(Alias::new(&self.table))
+            .and_where(condition)
+            .to_owned();
+
+        self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Projector for RolePermissionProjector {
+    fn name(&self) -> &str {
+        "role_permissions"
+    }
+
+    async fn handle_event(&self, event: &StoredEvent) -> Result<()> {
+        match event.event_type.as_str() {
+            "RolePermissionGranted" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let granted = match payload {
+                    IdentityAccessEvent::RolePermissionGranted(granted) => granted,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                let statement = Query::insert()
+                    .into_table(Alias::new(&self.table))
+                    .columns([
+                        role_permission_view::Column::RoleId,
+                        role_permission_view::Column::PermissionId,
+                        role_permission_view::Column::CreatedAt,
+                    ])
+                    .values_panic([
+                        granted.role_id.into(),
+                        granted.permission_id.into(),
+                        event.created_at.into(),
+                    ])
+                    .on_conflict(
+                        OnConflict::columns([
+                            role_permission_view::Column::RoleId,
+                            role_permission_view::Column::PermissionId,
+                        ])
+                        .update_column(role_permission_view::Column::RoleId)
+                        .to_owned(),
+                    )
+                    .to_owned();
+
+                self.db.execute(self.db.get_database_backend().build(&statement)).await?;
+            }
+            "RolePermissionRevoked" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let revoked = match payload {
+                    IdentityAccessEvent::RolePermissionRevoked(revoked) => revoked,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.delete_grants(
+                    Expr::col(role_permission_view::Column::RoleId).eq(revoked.role_id)
+                        .and(Expr::col(role_permission_view::Column::PermissionId).eq(revoked.permission_id)),
+                ).await?;
+            }
+            "PermissionDeleted" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let permission_deleted = match payload {
+                    IdentityAccessEvent::PermissionDeleted(permission_deleted) => permission_deleted,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                // Roles keep the grant in their own stream, but a deleted permission grants nothing.
+                self.delete_grants(
+                    Expr::col(role_permission_view::Column::PermissionId).eq(permission_deleted.permission_id),
+                ).await?;
+            }
+            "RoleDeleted" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let role_deleted = match payload {
+                    IdentityAccessEvent::RoleDeleted(role_deleted) => role_deleted,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.delete_grants(
+                    Expr::col(role_permission_view::Column::RoleId).eq(role_deleted.role_id),
+                ).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn reset(&self) -> Result<()> {
+        execute_ddl(&self.db, format!("TRUNCATE TABLE `{}`", self.table)).await
+    }
+
+    async fn create_shadow(&self) -> Result<Option<Arc<dyn Projector>>> {
+        create_shadow_table(&self.db, &self.table).await?;
+
+        Ok(Some(Arc::new(RolePermissionProjector {
+            db: self.db.clone(),
+            table: format!("{}{}", self.table, SHADOW_SUFFIX),
+        })))
+    }
+
+    async fn promote_shadow(&self) -> Result<()> {
+        promote_shadow_table(&self.db, &self.table).await
+    }
+}
+
+/// Projects role assignments, role grants and the permission tree into `user_effective_permissions`,
+/// one row per permission a user holds through each grant that covers it.
+///
+/// The projection keeps its own copy of the assignments, grants and tree in `authz_*` tables rather
+/// than reading the other read models, which may be behind or ahead of it. Whenever one of them
+/// changes, the rows of every affected user are recomputed from that copy.
+pub struct EffectivePermissionProjector {
+    db: DatabaseConnection,
+    table: String,
+    permissions: String,
+    role_permissions: String,
+    user_roles: String,
+}
+
+impl EffectivePermissionProjector {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self::with_suffix(db, "")
+    }
+
+    fn with_suffix(db: DatabaseConnection, suffix: &str) -> Self {
+        Self {
+            db,
+            table: format!("user_effective_permissions{suffix}"),
+            permissions: format!("authz_permissions{suffix}"),
+            role_permissions: format!("authz_role_permissions{suffix}"),
+            user_roles: format!("authz_user_roles{suffix}"),
+        }
+    }
+
+    fn tables(&self) -> [&str; 4] {
+        [&self.table, &self.permissions, &self.role_permissions, &self.user_roles]
+    }
+
+    async fn execute(&self, sql: String, values: Vec<sea_orm::Value>) -> Result<()> {
+        self.db.execute(Statement::from_sql_and_values(self.db.get_database_backend(), sql, values)).await?;
+        Ok(())
+    }
+
+    /// Runs a query selecting a single `user_id` column.
+    async fn user_ids(&self, sql: String, values: Vec<sea_orm::Value>) -> Result<Vec<uuid::Uuid>> {
+        let rows = self.db
+            .query_all(Statement::from_sql_and_values(self.db.get_database_backend(), sql, values))
+            .await?;
+        rows.iter().map(|row| Ok(row.try_get("", "user_id")?)).collect()
+    }
+
+    async fn users_with_role(&self, role_id: uuid::Uuid) -> Result<Vec<uuid::Uuid>> {
+        self.user_ids(
+            format!("SELECT user_id FROM `{}` WHERE role_id = ?", self.user_roles),
+            vec![role_id.into()],
+        ).await
+    }
+
+    async fn users_holding(&self, permission_id: uuid::Uuid) -> Result<Vec<uuid::Uuid>> {
+        self.user_ids(
+            format!("SELECT DISTINCT user_id FROM `{}` WHERE permission_id = ?", self.table),
+            vec![permission_id.into()],
+        ).await
+    }
+
+    /// Recomputes the effective permissions of `user_ids`: every permission granted to one of their
+    /// roles, and every permission below it in the tree.
+    async fn refresh(&self, user_ids: Vec<uuid::Uuid>) -> Result<()> {
+        for user_ids in user_ids.chunks(500) {
+            let placeholders = vec!["?"; user_ids.len()].join(", ");
+            let values: Vec<sea_orm::Value> = user_ids.iter().map(|&user_id| user_id.into()).collect();
+
+            let txn = self.db.begin().await?;
+            txn.execute(Statement::from_sql_and_values(
+                txn.get_database_backend(),
+                format!("DELETE FROM `{}` WHERE user_id IN ({placeholders})", self.table),
+                values.clone(),
+            )).await?;
+            txn.execute(Statement::from_sql_and_values(
+                txn.get_database_backend(),
+                format!(
+                    "INSERT INTO `{table}` (user_id, permission_id, role_id, granted_permission_id, code) \
+                     SELECT ur.user_id, p.id, ur.role_id, g.id, p.code \
+                     FROM `{user_roles}` ur \
+                     JOIN `{role_permissions}` rp ON rp.role_id = ur.role_id \
+                     JOIN `{permissions}` g ON g.id = rp.permission_id \
+                     JOIN `{permissions}` p ON p.path LIKE CONCAT(g.path, '%') \
+                     WHERE ur.user_id IN ({placeholders})",
+                    table = self.table,
+                    user_roles = self.user_roles,
+                    role_permissions = self.role_permissions,
+                    permissions = self.permissions,
+                ),
+                values,
+            )).await?;
+            txn.commit().await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Projector for EffectivePermissionProjector {
+    fn name(&self) -> &str {
+        "effective_permissions"
+    }
+
+    async fn handle_event(&self, event: &StoredEvent) -> Result<()> {
+        match event.event_type.as_str() {
+            "PermissionCreated" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let permission_created = match payload {
+                    IdentityAccessEvent::PermissionCreated(permission_created) => permission_created,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                let parent_path = match permission_created.parent_id {
+                    Some(parent_id) => {
+                        let row = self.db.query_one(Statement::from_sql_and_values(
+                            self.db.get_database_backend(),
+                            format!("SELECT path FROM `{}` WHERE id = ?", self.permissions),
+                            vec![parent_id.into()],
+                        )).await?
+                            .ok_or_else(|| anyhow::anyhow!("Permission {} not found", parent_id))?;
+                        row.try_get("", "path")?
+                    }
+                    None => "/".to_string(),
+                };
+                let path = format!("{}{}", parent_path, path_segment(permission_created.permission_id));
+
+                self.execute(
+                    format!("INSERT INTO `{}` (id, code, path) VALUES (?, ?, ?) ON DUPLICATE KEY UPDATE id = id", self.permissions),
+                    vec![
+                        permission_created.permission_id.into(),
+                        permission_created.code.into(),
+                        path.into(),
+                    ],
+                ).await?;
+
+                // Whoever holds the parent menu inherits the new permission.
+                if let Some(parent_id) = permission_created.parent_id {
+                    self.refresh(self.users_holding(parent_id).await?).await?;
+                }
+            }
+            "PermissionDeleted" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let permission_deleted = match payload {
+                    IdentityAccessEvent::PermissionDeleted(permission_deleted) => permission_deleted,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+                let permission_id = permission_deleted.permission_id;
+
+                // Holding anything through a grant of the permission means holding the permission itself.
+                let user_ids = self.users_holding(permission_id).await?;
+                self.execute(format!("DELETE FROM `{}` WHERE id = ?", self.permissions), vec![permission_id.into()]).await?;
+                self.execute(
+                    format!("DELETE FROM `{}` WHERE permission_id = ?", self.role_permissions),
+                    vec![permission_id.into()],
+                ).await?;
+                self.refresh(user_ids).await?;
+            }
+            "RolePermissionGranted" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let granted = match payload {
+                    IdentityAccessEvent::RolePermissionGranted(granted) => granted,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.execute(
+                    format!(
+                        "INSERT INTO `{}` (role_id, permission_id) VALUES (?, ?) ON DUPLICATE KEY UPDATE role_id = role_id",
+                        self.role_permissions,
+                    ),
+                    vec![granted.role_id.into(), granted.permission_id.into()],
+                ).await?;
+                self.refresh(self.users_with_role(granted.role_id).await?).await?;
+            }
+            "RolePermissionRevoked" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let revoked = match payload {
+                    IdentityAccessEvent::RolePermissionRevoked(revoked) => revoked,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.execute(
+                    format!("DELETE FROM `{}` WHERE role_id = ? AND permission_id = ?", self.role_permissions),
+                    vec![revoked.role_id.into(), revoked.permission_id.into()],
+                ).await?;
+                self.refresh(self.users_with_role(revoked.role_id).await?).await?;
+            }
+            "RoleDeleted" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let role_deleted = match payload {
+                    IdentityAccessEvent::RoleDeleted(role_deleted) => role_deleted,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                // A deleted role stays assigned in its users' streams but grants nothing.
+                self.execute(
+                    format!("DELETE FROM `{}` WHERE role_id = ?", self.role_permissions),
+                    vec![role_deleted.role_id.into()],
+                ).await?;
+                self.refresh(self.users_with_role(role_deleted.role_id).await?).await?;
+            }
+            "UserRoleAssigned" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let assigned = match payload {
+                    IdentityAccessEvent::UserRoleAssigned(assigned) => assigned,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.execute(
+                    format!(
+                        "INSERT INTO `{}` (user_id, role_id) VALUES (?, ?) ON DUPLICATE KEY UPDATE user_id = user_id",
+                        self.user_roles,
+                    ),
+                    vec![assigned.user_id.into(), assigned.role_id.into()],
+                ).await?;
+                self.refresh(vec![assigned.user_id]).await?;
+            }
+            "UserRoleRemoved" => {
+                let payload: IdentityAccessEvent = serde_json::from_value(event.payload.clone())?;
+                let removed = match payload {
+                    IdentityAccessEvent::UserRoleRemoved(removed) => removed,
+                    _ => return Err(anyhow::anyhow!("Invalid event type")),
+                };
+
+                self.execute(
+                    format!("DELETE FROM `{}` WHERE user_id = ? AND role_id = ?", self.user_roles),
+                    vec![removed.user_id.into(), removed.role_id.into()],
+                ).await?;
+                self.refresh(vec![removed.user_id]).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn reset(&self) -> Result<()> {
+        for table in self.tables() {
+            execute_ddl(&self.db, format!("TRUNCATE TABLE `{}`", table)).await?;
+        }
+        Ok(())
+    }
+
+    async fn create_shadow(&self) -> Result<Option<Arc<dyn Projector>>> {
+        for table in self.tables() {
+            create_shadow_table(&self.db, table).await?;
+        }
+
+        Ok(Some(Arc::new(EffectivePermissionProjector::with_suffix(self.db.clone(), SHADOW_SUFFIX))))
+    }
+
+    async fn promote_shadow(&self) -> Result<()> {
+        for table in self.tables() {
+            promote_shadow_table(&self.db, table).await?;
+        }
+        Ok(())
+    }
+}
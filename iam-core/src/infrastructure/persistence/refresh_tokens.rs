@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// A refresh token as stored: only the SHA-256 hash of the token handed to the client is kept.
+///
+/// Every token rotated from the one issued at login belongs to the same family, so a
+/// stolen token can be cut off together with everything issued after it.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct RefreshToken {
+    pub token_hash: String,
+    pub family_id: Uuid,
+    pub user_id: Uuid,
+    pub tenant_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    /// Set once the token has been exchanged for its successor.
+    pub rotated_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Trait for persisting refresh tokens.
+#[async_trait]
+pub trait RefreshTokenStore: Send + Sync {
+    async fn save_token(&self, token: &RefreshToken) -> Result<(), AppError>;
+
+    async fn find_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError>;
+
+    /// Marks the token as rotated, unless it already is or has been revoked.
+    /// Returns whether this call rotated it, so only one of two concurrent uses succeeds.
+    async fn mark_rotated(&self, token_hash: &str, at: DateTime<Utc>) -> Result<bool, AppError>;
+
+    /// Revokes every token of the family that is not revoked yet.
+    async fn revoke_family(&self, family_id: Uuid, at: DateTime<Utc>) -> Result<(), AppError>;
+
+    /// Revokes every token of the user that is not revoked yet.
+    async fn revoke_user(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<(), AppError>;
+}
+
+pub struct SqlxRefreshTokenStore {
+    pool: MySqlPool,
+}
+
+impl SqlxRefreshTokenStore {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenStore for SqlxRefreshTokenStore {
+    async fn save_token(&self, token: &RefreshToken) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO refresh_tokens (token_hash, family_id, user_id, tenant_id, expires_at, created_at, rotated_at, revoked_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&token.token_hash)
+        .bind(token.family_id)
+        .bind(token.user_id)
+        .bind(token.tenant_id)
+        .bind(token.expires_at)
+        .bind(token.created_at)
+        .bind(token.rotated_at)
+        .bind(token.revoked_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError> {
+        let token = sqlx::query_as::<_, RefreshToken>(
+            "SELECT token_hash, family_id, user_id, tenant_id, expires_at, created_at, rotated_at, revoked_at \
+             FROM refresh_tokens WHERE token_hash = ?"
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    async fn mark_rotated(&self, token_hash: &str, at: DateTime<Utc>) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE refresh_tokens SET rotated_at = ? WHERE token_hash = ? AND rotated_at IS NULL AND revoked_at IS NULL"
+        )
+        .bind(at)
+        .bind(token_hash)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn revoke_family(&self, family_id: Uuid, at: DateTime<Utc>) -> Result<(), AppError> {
+        sqlx::query("UPDATE refresh_tokens SET revoked_at = ? WHERE family_id = ? AND revoked_at IS NULL")
+            .bind(at)
+            .bind(family_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn revoke_user(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<(), AppError> {
+        sqlx::query("UPDATE refresh_tokens SET revoked_at = ? WHERE user_id = ? AND revoked_at IS NULL")
+            .bind(at)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// A `RefreshTokenStore` kept in memory, for tests and local development.
+#[derive(Default)]
+pub struct InMemoryRefreshTokenStore {
+    tokens: RwLock<HashMap<String, RefreshToken>>,
+}
+
+impl InMemoryRefreshTokenStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RefreshTokenStore for InMemoryRefreshTokenStore {
+    async fn save_token(&self, token: &RefreshToken) -> Result<(), AppError> {
+        self.tokens.write().await.insert(token.token_hash.clone(), token.clone());
+        Ok(())
+    }
+
+    async fn find_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError> {
+        Ok(self.tokens.read().await.get(token_hash).cloned())
+    }
+
+    async fn mark_rotated(&self, token_hash: &str, at: DateTime<Utc>) -> Result<bool, AppError> {
+        match self.tokens.write().await.get_mut(token_hash) {
+            Some(token) if token.rotated_at.is_none() && token.revoked_at.is_none() => {
+                token.rotated_at = Some(at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn revoke_family(&self, family_id: Uuid, at: DateTime<Utc>) -> Result<(), AppError> {
+        for token in self.tokens.write().await.values_mut() {
+            if token.family_id == family_id && token.revoked_at.is_none() {
+                token.revoked_at = Some(at);
+            }
+        }
+        Ok(())
+    }
+
+    async fn revoke_user(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<(), AppError> {
+        for token in self.tokens.write().await.values_mut() {
+            if token.user_id == user_id && token.revoked_at.is_none() {
+                token.revoked_at = Some(at);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::identity_access::aggregates::Aggregate;
+use crate::domain::identity_access::events::IdentityAccessEvent;
+use crate::error::AppError;
+use crate::infrastructure::persistence::event_store::{EventMetadata, EventStore, StoredEvent, deserialize_events};
+use crate::infrastructure::persistence::snapshot_store::{Snapshot, SnapshotPolicy};
+
+/// How often a command is re-run after losing an optimistic concurrency race.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one; 1 disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for every further retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff,
+            max_backoff,
+        }
+    }
+
+    /// A policy that lets every conflict through to the caller.
+    pub fn none() -> Self {
+        Self::new(1, Duration::ZERO, Duration::ZERO)
+    }
+
+    /// The delay before retrying after the given failed attempt (starting at 1).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Loads and saves aggregates of type `A` through the event store.
+///
+/// Loading starts from the latest snapshot when snapshots are enabled and replays the
+/// newer events; saving appends at the version the aggregate was loaded with, so a
+/// concurrent write surfaces as `AppError::ConcurrencyConflict`. Commands run through
+/// `execute` are retried on such conflicts according to the `RetryPolicy`.
+pub struct EventSourcedRepository<A: Aggregate> {
+    event_store: Arc<dyn EventStore>,
+    snapshots: Option<SnapshotPolicy>,
+    retry: RetryPolicy,
+    _aggregate: PhantomData<fn() -> A>,
+}
+
+impl<A: Aggregate> EventSourcedRepository<A> {
+    pub fn new(event_store: Arc<dyn EventStore>) -> Self {
+        Self {
+            event_store,
+            snapshots: None,
+            retry: RetryPolicy::none(),
+            _aggregate: PhantomData,
+        }
+    }
+
+    /// Restores aggregates from their latest snapshot and writes new snapshots according to `policy`.
+    pub fn with_snapshots(mut self, policy: SnapshotPolicy) -> Self {
+        self.snapshots = Some(policy);
+        self
+    }
+
+    /// Retries commands run through `execute` that hit a concurrency conflict.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Loads the aggregate, runs `command` against it and saves the resulting events.
+    ///
+    /// When another writer appended to the stream in the meantime, the aggregate is
+    /// reloaded and `command` is run again on the fresh state, up to the policy's
+    /// maximum number of attempts. `command` must therefore only decide on events and
+    /// have no side effects of its own; the services route every change to an existing
+    /// aggregate through here, so their checks always run against the latest state.
+    /// Returns the aggregate with the events applied.
+    pub async fn execute<F>(&self, id: Uuid, metadata: &EventMetadata, command: F) -> Result<A, AppError>
+    where
+        F: Fn(&A) -> Result<Vec<IdentityAccessEvent>, AppError>,
+    {
+        let mut attempt = 1;
+        loop {
+            let mut aggregate = self.load(id).await?;
+            let events = command(&aggregate)?;
+
+            match self.save(&mut aggregate, &events, metadata).await {
+                Err(AppError::ConcurrencyConflict) if attempt < self.retry.max_attempts => {
+                    let backoff = self.retry.backoff(attempt);
+                    tracing::debug!(
+                        "Concurrency conflict on {} {} (attempt {}), retrying in {:?}",
+                        A::AGGREGATE_TYPE, id, attempt, backoff
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+                Ok(()) => return Ok(aggregate),
+            }
+        }
+    }
+
+    /// Reconstructs the aggregate from its latest snapshot and the events after it.
+    pub async fn load(&self, id: Uuid) -> Result<A, AppError> {
+        if let Some(policy) = &self.snapshots
+            && let Some(snapshot) = policy.store.load_snapshot(id).await?
+        {
+            match serde_json::from_value::<A>(snapshot.state) {
+                Ok(mut aggregate) => {
+                    let stored_events = self.event_store.load_events_after(id, snapshot.version).await?;
+                    for event in self.deserialize(id, &stored_events)? {
+                        aggregate.apply(&event);
+                    }
+                    return Ok(aggregate);
+                }
+                // A snapshot written by an older version of the aggregate is only a cache;
+                // fall back to the full event stream instead of failing the command.
+                Err(e) => tracing::warn!(
+                    "Ignoring unreadable snapshot of {} {}: {}", A::AGGREGATE_TYPE, id, e
+                ),
+            }
+        }
+
+        let stored_events = self.event_store.load_events(id).await.map_err(|e| match e {
+            AppError::AggregateNotFound(_) => AppError::AggregateNotFound(format!("{} {}", A::AGGREGATE_TYPE, id)),
+            other => other,
+        })?;
+
+        Ok(A::from_events(&self.deserialize(id, &stored_events)?))
+    }
+
+    /// Applies `events` to the aggregate and appends them at the version it was loaded with.
+    /// Pass `A::default()` to save the first events of a new aggregate.
+    pub async fn save(&self, aggregate: &mut A, events: &[IdentityAccessEvent], metadata: &EventMetadata) -> Result<(), AppError> {
+        let expected_version = aggregate.version();
+        for event in events {
+            aggregate.apply(event);
+        }
+
+        self.event_store.save_events(aggregate.id(), events, expected_version, metadata).await?;
+
+        if let Some(policy) = &self.snapshots
+            && policy.is_due(expected_version, aggregate.version())
+        {
+            let snapshot = Snapshot::new(aggregate.id(), A::AGGREGATE_TYPE, aggregate.version(), serde_json::to_value(&*aggregate)?);
+            // The events are already committed, so a failed snapshot only costs a longer replay later.
+            if let Err(e) = policy.store.save_snapshot(&snapshot).await {
+                tracing::warn!("Failed to save snapshot of {} {}: {}", A::AGGREGATE_TYPE, aggregate.id(), e);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn deserialize(&self, id: Uuid, stored_events: &[StoredEvent]) -> Result<Vec<IdentityAccessEvent>, AppError> {
+        // Stored events are written by this service, so failing to read them is a server-side problem.
+        deserialize_events(stored_events).map_err(|e| {
+            AppError::InternalError(format!("Failed to deserialize events of {} {}: {}", A::AGGREGATE_TYPE, id, e))
+        })
+    }
+}
@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// Trait for a snapshot store.
+#[async_trait]
+pub trait SnapshotStore: Send + Sync {
+    /// Saves a snapshot, replacing the stored one unless it is newer.
+    async fn save_snapshot(&self, snapshot: &Snapshot) -> Result<(), AppError>;
+
+    /// Loads the latest snapshot for a given aggregate, if any.
+    async fn load_snapshot(&self, aggregate_id: Uuid) -> Result<Option<Snapshot>, AppError>;
+}
+
+/// The serialized state of an aggregate at a given version.
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct Snapshot {
+    pub aggregate_id: Uuid,
+    pub aggregate_type: String,
+    pub version: u64,
+    pub state: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Snapshot {
+    pub fn new(aggregate_id: Uuid, aggregate_type: &str, version: u64, state: serde_json::Value) -> Self {
+        Self {
+            aggregate_id,
+            aggregate_type: aggregate_type.to_string(),
+            version,
+            state,
+            created_at: chrono::Utc::now(),
+        }
+    }
+}
+
+/// Decides when a command service writes a new snapshot.
+#[derive(Clone)]
+pub struct SnapshotPolicy {
+    pub store: Arc<dyn SnapshotStore>,
+    /// A snapshot is taken every `frequency` events; 0 disables snapshotting.
+    pub frequency: u64,
+}
+
+impl SnapshotPolicy {
+    pub fn new(store: Arc<dyn SnapshotStore>, frequency: u64) -> Self {
+        Self { store, frequency }
+    }
+
+    /// Whether appending events moved the aggregate across a snapshot boundary.
+    pub fn is_due(&self, previous_version: u64, new_version: u64) -> bool {
+        self.frequency > 0 && new_version / self.frequency > previous_version / self.frequency
+    }
+}
+
+use sqlx::MySqlPool;
+
+pub struct SqlxSnapshotStore {
+    pool: MySqlPool,
+}
+
+impl SqlxSnapshotStore {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for SqlxSnapshotStore {
+    async fn save_snapshot(&self, snapshot: &Snapshot) -> Result<(), AppError> {
+        // `version` is assigned last so the IF() checks still see the stored version.
+        sqlx::query(
+            "INSERT INTO snapshots (aggregate_id, aggregate_type, version, state, created_at) VALUES (?, ?, ?, ?, ?) \
+             ON DUPLICATE KEY UPDATE \
+                 state = IF(VALUES(version) > version, VALUES(state), state), \
+                 created_at = IF(VALUES(version) > version, VALUES(created_at), created_at), \
+                 version = GREATEST(version, VALUES(version))"
+        )
+        .bind(snapshot.aggregate_id)
+        .bind(&snapshot.aggregate_type)
+        .bind(snapshot.version)
+        .bind(&snapshot.state)
+        .bind(snapshot.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn load_snapshot(&self, aggregate_id: Uuid) -> Result<Option<Snapshot>, AppError> {
+        let snapshot = sqlx::query_as::<_, Snapshot>(
+            "SELECT aggregate_id, aggregate_type, version, state, created_at FROM snapshots WHERE aggregate_id = ?"
+        )
+        .bind(aggregate_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+}
+
+/// A `SnapshotStore` kept in memory, for tests and local development.
+#[derive(Default)]
+pub struct InMemorySnapshotStore {
+    snapshots: RwLock<HashMap<Uuid, Snapshot>>,
+}
+
+impl InMemorySnapshotStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for InMemorySnapshotStore {
+    async fn save_snapshot(&self, snapshot: &Snapshot) -> Result<(), AppError> {
+        let mut snapshots = self.snapshots.write().await;
+
+        let is_newer = snapshots
+            .get(&snapshot.aggregate_id)
+            .is_none_or(|current| snapshot.version > current.version);
+        if is_newer {
+            snapshots.insert(snapshot.aggregate_id, snapshot.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn load_snapshot(&self, aggregate_id: Uuid) -> Result<Option<Snapshot>, AppError> {
+        Ok(self.snapshots.read().await.get(&aggregate_id).cloned())
+    }
+}
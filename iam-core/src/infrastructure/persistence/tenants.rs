@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// A tenant as stored in the `tenants` table.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct TenantRecord {
+    pub id: Uuid,
+    pub name: String,
+    pub code: String,
+    pub status: String,
+}
+
+impl TenantRecord {
+    pub fn is_active(&self) -> bool {
+        self.status == "active"
+    }
+}
+
+/// Trait for looking up the tenants requests are resolved to.
+#[async_trait]
+pub trait TenantStore: Send + Sync {
+    async fn find_tenant(&self, tenant_id: Uuid) -> Result<Option<TenantRecord>, AppError>;
+
+    /// Finds a tenant by its code, ignoring case.
+    async fn find_tenant_by_code(&self, code: &str) -> Result<Option<TenantRecord>, AppError>;
+}
+
+pub struct SqlxTenantStore {
+    pool: MySqlPool,
+}
+
+impl SqlxTenantStore {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TenantStore for SqlxTenantStore {
+    async fn find_tenant(&self, tenant_id: Uuid) -> Result<Option<TenantRecord>, AppError> {
+        let tenant = sqlx::query_as::<_, TenantRecord>("SELECT id, name, code, status FROM tenants WHERE id = ?")
+            .bind(tenant_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(tenant)
+    }
+
+    async fn find_tenant_by_code(&self, code: &str) -> Result<Option<TenantRecord>, AppError> {
+        // The column uses a case-insensitive collation
+        let tenant = sqlx::query_as::<_, TenantRecord>("SELECT id, name, code, status FROM tenants WHERE code = ?")
+            .bind(code)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(tenant)
+    }
+}
+
+/// A `TenantStore` kept in memory, for tests and local development.
+#[derive(Default)]
+pub struct InMemoryTenantStore {
+    tenants: RwLock<HashMap<Uuid, TenantRecord>>,
+}
+
+impl InMemoryTenantStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn save_tenant(&self, tenant: TenantRecord) {
+        self.tenants.write().await.insert(tenant.id, tenant);
+    }
+}
+
+#[async_trait]
+impl TenantStore for InMemoryTenantStore {
+    async fn find_tenant(&self, tenant_id: Uuid) -> Result<Option<TenantRecord>, AppError> {
+        Ok(self.tenants.read().await.get(&tenant_id).cloned())
+    }
+
+    async fn find_tenant_by_code(&self, code: &str) -> Result<Option<TenantRecord>, AppError> {
+        Ok(self
+            .tenants
+            .read()
+            .await
+            .values()
+            .find(|tenant| tenant.code.eq_ignore_ascii_case(code))
+            .cloned())
+    }
+}
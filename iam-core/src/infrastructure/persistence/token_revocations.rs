@@ -0,0 +1,156 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// Trait for recording access tokens that must no longer be accepted before they expire.
+///
+/// Entries are only needed until the tokens they cover have expired on their own,
+/// so every entry carries an expiry after which implementations drop it.
+#[async_trait]
+pub trait TokenRevocationStore: Send + Sync {
+    /// Revokes the token with the given `jti` until it expires at `expires_at`.
+    async fn revoke_token(&self, token_id: &str, expires_at: DateTime<Utc>) -> Result<(), AppError>;
+
+    /// Revokes every token issued to the user strictly before `issued_before`. The entry is kept
+    /// until `expires_at`, when all tokens issued before it have expired.
+    async fn revoke_user_tokens(&self, user_id: Uuid, issued_before: DateTime<Utc>, expires_at: DateTime<Utc>) -> Result<(), AppError>;
+
+    /// Whether a token with the given `jti`, issued to `user_id` at `issued_at`, has been revoked.
+    async fn is_revoked(&self, token_id: Option<&str>, user_id: Uuid, issued_at: DateTime<Utc>) -> Result<bool, AppError>;
+}
+
+pub struct SqlxTokenRevocationStore {
+    pool: MySqlPool,
+}
+
+impl SqlxTokenRevocationStore {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    /// Drops entries whose tokens have all expired, a bounded batch per call.
+    async fn purge_expired(&self) -> Result<(), AppError> {
+        let now = Utc::now();
+        sqlx::query("DELETE FROM revoked_tokens WHERE expires_at <= ? LIMIT 1000")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM user_token_revocations WHERE expires_at <= ? LIMIT 1000")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TokenRevocationStore for SqlxTokenRevocationStore {
+    async fn revoke_token(&self, token_id: &str, expires_at: DateTime<Utc>) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO revoked_tokens (jti, expires_at) VALUES (?, ?) \
+             ON DUPLICATE KEY UPDATE expires_at = GREATEST(expires_at, VALUES(expires_at))"
+        )
+        .bind(token_id)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        self.purge_expired().await
+    }
+
+    async fn revoke_user_tokens(&self, user_id: Uuid, issued_before: DateTime<Utc>, expires_at: DateTime<Utc>) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO user_token_revocations (user_id, issued_before, expires_at) VALUES (?, ?, ?) \
+             ON DUPLICATE KEY UPDATE \
+             issued_before = GREATEST(issued_before, VALUES(issued_before)), \
+             expires_at = GREATEST(expires_at, VALUES(expires_at))"
+        )
+        .bind(user_id)
+        .bind(issued_before)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        self.purge_expired().await
+    }
+
+    async fn is_revoked(&self, token_id: Option<&str>, user_id: Uuid, issued_at: DateTime<Utc>) -> Result<bool, AppError> {
+        let now = Utc::now();
+        let revoked: Option<i32> = sqlx::query_scalar(
+            "SELECT 1 FROM revoked_tokens WHERE jti = ? AND expires_at > ? \
+             UNION ALL \
+             SELECT 1 FROM user_token_revocations WHERE user_id = ? AND issued_before > ? AND expires_at > ? \
+             LIMIT 1"
+        )
+        .bind(token_id.unwrap_or_default())
+        .bind(now)
+        .bind(user_id)
+        .bind(issued_at)
+        .bind(now)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(revoked.is_some())
+    }
+}
+
+#[derive(Default)]
+struct Revocations {
+    tokens: HashMap<String, DateTime<Utc>>,
+    /// Per user: tokens issued before the first value are revoked until the second.
+    users: HashMap<Uuid, (DateTime<Utc>, DateTime<Utc>)>,
+}
+
+/// A `TokenRevocationStore` kept in memory, for tests and single-instance deployments.
+#[derive(Default)]
+pub struct InMemoryTokenRevocationStore {
+    revocations: RwLock<Revocations>,
+}
+
+impl InMemoryTokenRevocationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TokenRevocationStore for InMemoryTokenRevocationStore {
+    async fn revoke_token(&self, token_id: &str, expires_at: DateTime<Utc>) -> Result<(), AppError> {
+        let now = Utc::now();
+        let mut revocations = self.revocations.write().await;
+        revocations.tokens.retain(|_, expires_at| *expires_at > now);
+        let entry = revocations.tokens.entry(token_id.to_string()).or_insert(expires_at);
+        *entry = (*entry).max(expires_at);
+        Ok(())
+    }
+
+    async fn revoke_user_tokens(&self, user_id: Uuid, issued_before: DateTime<Utc>, expires_at: DateTime<Utc>) -> Result<(), AppError> {
+        let now = Utc::now();
+        let mut revocations = self.revocations.write().await;
+        revocations.users.retain(|_, (_, expires_at)| *expires_at > now);
+        let entry = revocations.users.entry(user_id).or_insert((issued_before, expires_at));
+        *entry = (entry.0.max(issued_before), entry.1.max(expires_at));
+        Ok(())
+    }
+
+    async fn is_revoked(&self, token_id: Option<&str>, user_id: Uuid, issued_at: DateTime<Utc>) -> Result<bool, AppError> {
+        let now = Utc::now();
+        let revocations = self.revocations.read().await;
+
+        let token_revoked = token_id
+            .and_then(|token_id| revocations.tokens.get(token_id))
+            .is_some_and(|expires_at| *expires_at > now);
+        let user_revoked = revocations
+            .users
+            .get(&user_id)
+            .is_some_and(|(issued_before, expires_at)| issued_at < *issued_before && *expires_at > now);
+
+        Ok(token_revoked || user_revoked)
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::infrastructure::persistence::event_store::StoredEvent;
+
+/// Migrates the payload of one event type from one schema version to the next.
+pub trait Upcaster: Send + Sync {
+    /// The event type this upcaster applies to, e.g. `"UserRegistered"`.
+    fn event_type(&self) -> &str;
+
+    /// The schema version it reads; the result has version `source_version() + 1`.
+    fn source_version(&self) -> u32;
+
+    /// Transforms the event data, i.e. the object inside the `{"<EventType>": {...}}` payload.
+    fn upcast(&self, data: serde_json::Value) -> Result<serde_json::Value, AppError>;
+}
+
+/// An `Upcaster` backed by a function, for migrations that need no state.
+pub struct FnUpcaster<F> {
+    event_type: String,
+    source_version: u32,
+    upcast: F,
+}
+
+impl<F> FnUpcaster<F>
+where
+    F: Fn(serde_json::Value) -> Result<serde_json::Value, AppError> + Send + Sync,
+{
+    pub fn new(event_type: &str, source_version: u32, upcast: F) -> Self {
+        Self {
+            event_type: event_type.to_string(),
+            source_version,
+            upcast,
+        }
+    }
+}
+
+impl<F> Upcaster for FnUpcaster<F>
+where
+    F: Fn(serde_json::Value) -> Result<serde_json::Value, AppError> + Send + Sync,
+{
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn source_version(&self) -> u32 {
+        self.source_version
+    }
+
+    fn upcast(&self, data: serde_json::Value) -> Result<serde_json::Value, AppError> {
+        (self.upcast)(data)
+    }
+}
+
+/// The upcasters known to an event store, applied in version order whenever events are read.
+#[derive(Clone, Default)]
+pub struct UpcasterRegistry {
+    upcasters: HashMap<(String, u32), Arc<dyn Upcaster>>,
+}
+
+impl UpcasterRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an upcaster, replacing any previous one for the same event type and version.
+    pub fn register(mut self, upcaster: impl Upcaster + 'static) -> Self {
+        let key = (upcaster.event_type().to_string(), upcaster.source_version());
+        self.upcasters.insert(key, Arc::new(upcaster));
+        self
+    }
+
+    /// Brings a stored event up to the newest schema version the registry knows about.
+    pub fn upcast(&self, event: &mut StoredEvent) -> Result<(), AppError> {
+        while let Some(upcaster) = self.upcasters.get(&(event.event_type.clone(), event.schema_version)) {
+            let data = event
+                .payload
+                .get_mut(&event.event_type)
+                .ok_or_else(|| AppError::InternalError(format!(
+                    "Event {} has no {} payload to upcast", event.id, event.event_type
+                )))?;
+
+            *data = upcaster.upcast(data.take())?;
+            event.schema_version += 1;
+        }
+
+        Ok(())
+    }
+
+    pub fn upcast_all(&self, events: &mut [StoredEvent]) -> Result<(), AppError> {
+        events.iter_mut().try_for_each(|event| self.upcast(event))
+    }
+}
+
+/// The upcasters for the Identity & Access event history.
+///
+/// Every change to the shape of an `IdentityAccessEvent` variant bumps its
+/// `schema_version` and registers the migration from the previous version here.
+pub fn identity_access_upcasters() -> UpcasterRegistry {
+    UpcasterRegistry::new()
+}
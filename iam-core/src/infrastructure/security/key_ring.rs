@@ -0,0 +1,303 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, EllipticCurveKeyParameters, EllipticCurveKeyType, Jwk,
+    JwkSet, KeyAlgorithm, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::rand::SystemRandom;
+use ring::signature::{EcdsaKeyPair, KeyPair, RsaKeyPair, RsaPublicKeyComponents, ECDSA_P256_SHA256_FIXED_SIGNING};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+use crate::config::JwtConfig;
+use crate::error::AppError;
+
+/// A key that signs and verifies access tokens.
+///
+/// Asymmetric keys are loaded from a private key PEM file: RSA keys sign with RS256 and
+/// P-256 keys with ES256. Their public half is published as a JWK so that other services
+/// can verify tokens without holding anything secret.
+pub struct JwtKey {
+    kid: Option<String>,
+    algorithm: Algorithm,
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    jwk: Option<Jwk>,
+    active_from: DateTime<Utc>,
+}
+
+impl JwtKey {
+    /// A shared HS256 secret. It has no `kid` and is never published.
+    pub fn hmac(secret: &str) -> Self {
+        Self {
+            kid: None,
+            algorithm: Algorithm::HS256,
+            encoding: EncodingKey::from_secret(secret.as_bytes()),
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+            jwk: None,
+            active_from: DateTime::<Utc>::MIN_UTC,
+        }
+    }
+
+    /// Loads an RSA (PKCS#1 or PKCS#8) or P-256 (PKCS#8) private key. The key is published
+    /// right away but only signs tokens from `active_from` on.
+    pub fn from_pem(kid: &str, pem: &[u8], active_from: DateTime<Utc>) -> Result<Self, AppError> {
+        let invalid = |reason: &str| AppError::InternalError(format!("Invalid signing key {}: {}", kid, reason));
+
+        let parsed = pem::parse(pem).map_err(|e| invalid(&e.to_string()))?;
+        let der = parsed.contents();
+        let rsa_key = match parsed.tag() {
+            "RSA PRIVATE KEY" => RsaKeyPair::from_der(der).ok(),
+            "PRIVATE KEY" => RsaKeyPair::from_pkcs8(der).ok(),
+            other => return Err(invalid(&format!("unsupported PEM block {}", other))),
+        };
+
+        let (algorithm, encoding, parameters) = if let Some(rsa_key) = rsa_key {
+            let components = RsaPublicKeyComponents::<Vec<u8>>::from(rsa_key.public());
+            let encoding = EncodingKey::from_rsa_pem(pem).map_err(|e| invalid(&e.to_string()))?;
+            let parameters = AlgorithmParameters::RSA(RSAKeyParameters {
+                key_type: RSAKeyType::RSA,
+                n: URL_SAFE_NO_PAD.encode(components.n),
+                e: URL_SAFE_NO_PAD.encode(components.e),
+            });
+            (Algorithm::RS256, encoding, parameters)
+        } else {
+            let ec_key = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, der, &SystemRandom::new())
+                .map_err(|_| invalid("expected an RSA key or a P-256 key in PKCS#8 form"))?;
+            // The public key is the uncompressed point: 0x04 followed by x and y.
+            let point = ec_key.public_key().as_ref();
+            let encoding = EncodingKey::from_ec_pem(pem).map_err(|e| invalid(&e.to_string()))?;
+            let parameters = AlgorithmParameters::EllipticCurve(EllipticCurveKeyParameters {
+                key_type: EllipticCurveKeyType::EC,
+                curve: EllipticCurve::P256,
+                x: URL_SAFE_NO_PAD.encode(&point[1..33]),
+                y: URL_SAFE_NO_PAD.encode(&point[33..65]),
+            });
+            (Algorithm::ES256, encoding, parameters)
+        };
+
+        let jwk = Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(if algorithm == Algorithm::RS256 { KeyAlgorithm::RS256 } else { KeyAlgorithm::ES256 }),
+                key_id: Some(kid.to_string()),
+                ..Default::default()
+            },
+            algorithm: parameters,
+        };
+        // Verification only ever needs the public half.
+        let decoding = DecodingKey::from_jwk(&jwk).map_err(|e| invalid(&e.to_string()))?;
+
+        Ok(Self { kid: Some(kid.to_string()), algorithm, encoding, decoding, jwk: Some(jwk), active_from })
+    }
+
+    pub fn kid(&self) -> Option<&str> {
+        self.kid.as_deref()
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    pub fn active_from(&self) -> DateTime<Utc> {
+        self.active_from
+    }
+}
+
+/// The format of the activation time in key file names, a UTC time such as `20261017T090000Z`.
+const ACTIVATION_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Where the key ring gets its keys from.
+enum KeySource {
+    Fixed,
+    /// Every `<kid>.pem` or `<kid>@<activation time>.pem` file in the directory.
+    Directory { path: PathBuf },
+}
+
+/// The keys access tokens are signed and verified with, selected by `kid`.
+///
+/// Keys are rotated without invalidating tokens or surprising other services:
+/// 1. Add the next key as `<kid>@<activation time>.pem` to the key directory, for example
+///    `2026-10@20261017T090000Z.pem`. It is picked up on the next reload and published in
+///    the JWKS, but does not sign tokens yet.
+/// 2. Once the activation time has passed, which should leave verifiers time to refresh
+///    their cached JWKS, it becomes the newest active key and signs all new tokens.
+///    A key named just `<kid>.pem` is active right away.
+/// 3. Remove the previous key once the last tokens it signed have expired.
+///
+/// `signing_key_id` pins the signing key instead, for example to roll back a rotation.
+pub struct KeyRing {
+    source: KeySource,
+    signing_key_id: Option<String>,
+    keys: RwLock<Arc<Vec<JwtKey>>>,
+}
+
+impl KeyRing {
+    /// Signs and verifies with the given keys only.
+    pub fn new(keys: Vec<JwtKey>, signing_key_id: Option<String>) -> Self {
+        Self { source: KeySource::Fixed, signing_key_id, keys: RwLock::new(Arc::new(keys)) }
+    }
+
+    /// Signs and verifies with a single shared HS256 secret.
+    pub fn hmac(secret: &str) -> Self {
+        Self::new(vec![JwtKey::hmac(secret)], None)
+    }
+
+    /// Loads the keys from the PEM files in `path`.
+    pub fn from_directory(path: impl Into<PathBuf>, signing_key_id: Option<String>) -> Result<Self, AppError> {
+        let path = path.into();
+        let keys = load_directory(&path)?;
+        let key_ring = Self {
+            source: KeySource::Directory { path },
+            signing_key_id,
+            keys: RwLock::new(Arc::new(keys)),
+        };
+        key_ring.active_key_id()?;
+        Ok(key_ring)
+    }
+
+    /// Uses the key directory when one is configured and the shared secret otherwise.
+    pub fn from_config(config: &JwtConfig) -> Result<Self, AppError> {
+        match &config.keys_dir {
+            Some(dir) => Self::from_directory(dir, config.signing_key_id.clone()),
+            None if config.secret.is_empty() => {
+                Err(AppError::InternalError("Either a JWT secret or a key directory is required".to_string()))
+            }
+            None => Ok(Self::hmac(&config.secret)),
+        }
+    }
+
+    /// Re-reads the key directory. On failure the current keys stay in use.
+    pub fn reload(&self) -> Result<(), AppError> {
+        if let KeySource::Directory { path } = &self.source {
+            let keys = load_directory(path)?;
+            *self.keys.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(keys);
+        }
+        Ok(())
+    }
+
+    /// Reloads the key directory in a background task, so new keys are published and
+    /// removed ones retired without a restart.
+    pub fn spawn_reload(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if let Err(e) = self.reload() {
+                    tracing::error!("Failed to reload signing keys: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Signs `claims` with the current signing key, naming it in the `kid` header.
+    pub fn sign<T: Serialize>(&self, claims: &T) -> Result<String, AppError> {
+        let keys = self.keys();
+        let key = select_signing_key(&keys, self.signing_key_id.as_deref(), Utc::now())?;
+
+        let mut header = Header::new(key.algorithm);
+        header.kid = key.kid.clone();
+
+        jsonwebtoken::encode(&header, claims, &key.encoding)
+            .map_err(|e| AppError::InternalError(format!("Failed to generate token: {}", e)))
+    }
+
+    /// Verifies `token` with the key named by its `kid` header and returns its claims.
+    /// The algorithm is taken from the key, never from the token.
+    pub fn verify<T: DeserializeOwned>(&self, token: &str) -> Result<T, AppError> {
+        let header = jsonwebtoken::decode_header(token)
+            .map_err(|e| AppError::AuthenticationError(format!("Invalid token: {}", e)))?;
+
+        let keys = self.keys();
+        let key = keys
+            .iter()
+            .find(|key| key.kid == header.kid && key.algorithm == header.alg)
+            .ok_or_else(|| AppError::AuthenticationError("Invalid token: unknown signing key".to_string()))?;
+
+        jsonwebtoken::decode::<T>(token, &key.decoding, &Validation::new(key.algorithm))
+            .map(|data| data.claims)
+            .map_err(|e| AppError::AuthenticationError(format!("Invalid token: {}", e)))
+    }
+
+    /// The public keys of every key in the ring, including keys that are not signing yet.
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet { keys: self.keys().iter().filter_map(|key| key.jwk.clone()).collect() }
+    }
+
+    /// The `kid` of the key new tokens are signed with.
+    pub fn active_key_id(&self) -> Result<Option<String>, AppError> {
+        let keys = self.keys();
+        select_signing_key(&keys, self.signing_key_id.as_deref(), Utc::now()).map(|key| key.kid.clone())
+    }
+
+    fn keys(&self) -> Arc<Vec<JwtKey>> {
+        self.keys.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// The pinned key if there is one, otherwise the most recently activated key. When no key
+/// is active yet, as on a first deployment, the key closest to activation is used.
+fn select_signing_key<'a>(keys: &'a [JwtKey], pinned: Option<&str>, now: DateTime<Utc>) -> Result<&'a JwtKey, AppError> {
+    let key = match pinned {
+        Some(kid) => keys.iter().find(|key| key.kid.as_deref() == Some(kid)),
+        None => keys
+            .iter()
+            .filter(|key| key.active_from <= now)
+            .max_by_key(|key| key.active_from)
+            .or_else(|| keys.iter().min_by_key(|key| key.active_from)),
+    };
+
+    key.ok_or_else(|| match pinned {
+        Some(kid) => AppError::InternalError(format!("Signing key {} not found", kid)),
+        None => AppError::InternalError("No signing key available".to_string()),
+    })
+}
+
+fn load_directory(path: &Path) -> Result<Vec<JwtKey>, AppError> {
+    let unreadable = |e: std::io::Error| {
+        AppError::InternalError(format!("Failed to read signing keys from {}: {}", path.display(), e))
+    };
+
+    let mut keys: Vec<JwtKey> = Vec::new();
+    for entry in std::fs::read_dir(path).map_err(unreadable)? {
+        let file = entry.map_err(unreadable)?.path();
+        let Some(stem) = file.extension().filter(|ext| *ext == "pem").and(file.file_stem()).and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let (kid, active_from) = parse_key_file_name(stem)?;
+        if keys.iter().any(|key| key.kid.as_deref() == Some(kid)) {
+            return Err(AppError::InternalError(format!("Signing key {} is defined by more than one file", kid)));
+        }
+
+        let pem = std::fs::read(&file).map_err(unreadable)?;
+        keys.push(JwtKey::from_pem(kid, &pem, active_from)?);
+    }
+
+    Ok(keys)
+}
+
+/// Splits a key file name without extension into the `kid` and the time the key starts
+/// signing: `<kid>@<activation time>`, or just `<kid>` for a key that is active right away.
+/// The time is part of the name so that copying or touching the file cannot change it.
+fn parse_key_file_name(stem: &str) -> Result<(&str, DateTime<Utc>), AppError> {
+    let Some((kid, activation_time)) = stem.rsplit_once('@') else {
+        return Ok((stem, DateTime::<Utc>::MIN_UTC));
+    };
+
+    let active_from = NaiveDateTime::parse_from_str(activation_time, ACTIVATION_TIME_FORMAT)
+        .map_err(|_| AppError::InternalError(format!(
+            "Invalid activation time {} of signing key {}, expected a UTC time such as 20261017T090000Z",
+            activation_time, kid
+        )))?
+        .and_utc();
+    if kid.is_empty() {
+        return Err(AppError::InternalError(format!("Signing key file {}.pem has no key ID", stem)));
+    }
+
+    Ok((kid, active_from))
+}
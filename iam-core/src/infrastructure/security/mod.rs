@@ -0,0 +1,3 @@
+pub mod key_ring;
+
+pub use key_ring::*;
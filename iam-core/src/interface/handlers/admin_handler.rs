@@ -0,0 +1,162 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::infrastructure::persistence::projection::{RebuildMode, RebuildStatus};
+use crate::interface::middleware::{auth::PlatformAdmin, AppState};
+
+#[derive(Debug, Clone, Copy, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RebuildModeRequest {
+    /// 清空现有读模型表后原地重放，重建期间读取到的数据不完整
+    InPlace,
+    /// 先重放到影子表，完成后原子替换，重建期间读取不受影响
+    Shadow,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct RebuildProjectionRequest {
+    /// 重建方式，默认为 shadow
+    pub mode: Option<RebuildModeRequest>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RebuildStatusResponse {
+    /// 投影名称
+    pub projection: String,
+    /// 重建方式：in_place 或 shadow
+    pub mode: String,
+    /// 状态：running、completed 或 failed
+    pub state: String,
+    /// 已重放到的事件位置
+    pub position: u64,
+    /// 重建开始时最新的事件位置
+    pub target_position: u64,
+    /// 已重放的事件数量
+    pub events_replayed: u64,
+    /// 失败原因
+    pub error: Option<String>,
+    /// 开始时间
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    /// 结束时间
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ProjectionResponse {
+    /// 投影名称
+    pub name: String,
+    /// 最近一次重建的状态
+    pub rebuild: Option<RebuildStatusResponse>,
+}
+
+impl From<RebuildStatus> for RebuildStatusResponse {
+    fn from(status: RebuildStatus) -> Self {
+        Self {
+            projection: status.projection,
+            mode: status.mode.as_str().to_string(),
+            state: status.state.as_str().to_string(),
+            position: status.position,
+            target_position: status.target_position,
+            events_replayed: status.events_replayed,
+            error: status.error,
+            started_at: status.started_at,
+            finished_at: status.finished_at,
+        }
+    }
+}
+
+/// 获取投影列表及其重建状态
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/projections",
+    tag = "admin",
+    responses(
+        (status = 200, description = "获取投影列表成功", body = Vec<ProjectionResponse>),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限")
+    )
+)]
+pub async fn list_projections(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+) -> Json<Vec<ProjectionResponse>> {
+    let rebuilder = &state.projection_rebuilder;
+    let projections = rebuilder
+        .projections()
+        .into_iter()
+        .map(|name| ProjectionResponse {
+            rebuild: rebuilder.status(&name).map(Into::into),
+            name,
+        })
+        .collect();
+
+    Json(projections)
+}
+
+/// 从事件存储重建投影
+///
+/// 重建在后台执行，通过状态接口查询进度
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/projections/{name}/rebuild",
+    tag = "admin",
+    params(
+        ("name" = String, Path, description = "投影名称")
+    ),
+    request_body = RebuildProjectionRequest,
+    responses(
+        (status = 202, description = "重建已开始", body = RebuildStatusResponse),
+        (status = 400, description = "投影正在重建或不支持该重建方式"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限"),
+        (status = 404, description = "投影不存在")
+    )
+)]
+pub async fn rebuild_projection(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+    Path(name): Path<String>,
+    Json(payload): Json<RebuildProjectionRequest>,
+) -> Result<(StatusCode, Json<RebuildStatusResponse>), AppError> {
+    let mode = match payload.mode.unwrap_or(RebuildModeRequest::Shadow) {
+        RebuildModeRequest::InPlace => RebuildMode::InPlace,
+        RebuildModeRequest::Shadow => RebuildMode::Shadow,
+    };
+
+    let status = state.projection_rebuilder.clone().start(&name, mode).await?;
+
+    Ok((StatusCode::ACCEPTED, Json(status.into())))
+}
+
+/// 查询投影重建进度
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/projections/{name}/rebuild",
+    tag = "admin",
+    params(
+        ("name" = String, Path, description = "投影名称")
+    ),
+    responses(
+        (status = 200, description = "获取重建状态成功", body = RebuildStatusResponse),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限"),
+        (status = 404, description = "该投影没有重建记录")
+    )
+)]
+pub async fn get_rebuild_status(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+    Path(name): Path<String>,
+) -> Result<Json<RebuildStatusResponse>, AppError> {
+    let status = state
+        .projection_rebuilder
+        .status(&name)
+        .ok_or_else(|| AppError::NotFound(format!("No rebuild of projection {} found", name)))?;
+
+    Ok(Json(status.into()))
+}
@@ -1,13 +1,16 @@
 use axum::{
     extract::State,
-    http::StatusCode,
-    response::Json,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json},
 };
+use chrono::SubsecRound;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::application::services::IssuedRefreshToken;
 use crate::error::AppError;
-use crate::interface::middleware::{AppState, auth::generate_token};
+use crate::infrastructure::persistence::event_store::EventMetadata;
+use crate::interface::middleware::{AppState, auth::{ensure_tenant_not_suspended, generate_token, AuthenticatedUser}, tenant::TenantContext};
 
 #[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
 pub struct LoginRequest {
@@ -17,6 +20,22 @@ pub struct LoginRequest {
     /// 密码
     #[validate(length(min = 1))]
     pub password: String,
+    /// 租户编码，未提供时从 X-Tenant-ID 头部或子域名解析租户
+    #[validate(length(min = 1, max = 100))]
+    pub tenant_code: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct RefreshTokenRequest {
+    /// 登录或上次刷新时返回的刷新令牌
+    #[validate(length(min = 1))]
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct LogoutRequest {
+    /// 同时吊销的刷新令牌，不提供时只吊销当前访问令牌
+    pub refresh_token: Option<String>,
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
@@ -27,6 +46,10 @@ pub struct LoginResponse {
     pub token_type: String,
     /// 过期时间（秒）
     pub expires_in: u64,
+    /// 刷新令牌，只能使用一次，刷新后返回新的刷新令牌
+    pub refresh_token: String,
+    /// 刷新令牌过期时间（秒）
+    pub refresh_expires_in: u64,
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
@@ -48,43 +71,66 @@ pub struct TokenInfo {
     responses(
         (status = 200, description = "登录成功", body = LoginResponse),
         (status = 400, description = "请求参数错误"),
-        (status = 401, description = "用户名或密码错误"),
+        (status = 401, description = "用户名或密码错误，或未指明租户"),
+        (status = 403, description = "租户未启用或请求指向不同租户"),
+        (status = 404, description = "租户不存在"),
+        (status = 423, description = "账户已锁定"),
+        (status = 429, description = "该客户端登录失败次数过多"),
         (status = 500, description = "服务器内部错误")
     )
 )]
 pub async fn login(
     State(state): State<AppState>,
+    headers: HeaderMap,
+    metadata: EventMetadata,
     Json(payload): Json<LoginRequest>,
 ) -> Result<(StatusCode, Json<LoginResponse>), AppError> {
     // 验证输入
     payload.validate()?;
 
-    // 1. 验证用户凭据（这里需要从请求中获取租户ID，暂时使用默认值）
-    // TODO: 在实际应用中，租户ID应该从请求头或子域名中获取
-    let tenant_id = uuid::Uuid::new_v4(); // 临时使用随机UUID
-    
-    let user = state.query_service
+    // 拒绝登录失败次数过多的客户端IP
+    state.login_guard.check_client(metadata.client_ip.as_deref()).await?;
+
+    // 1. 确定登录的租户：请求体中的租户编码，或 X-Tenant-ID 头部和子域名，同时提供时必须一致
+    let tenant = match (payload.tenant_code.as_deref(), TenantContext::resolve(&headers, None, &state).await?) {
+        (Some(code), Some(tenant)) if !code.eq_ignore_ascii_case(&tenant.code) => {
+            return Err(AppError::AuthorizationError("Request refers to conflicting tenants".to_string()));
+        }
+        (_, Some(tenant)) => tenant,
+        (Some(code), None) => TenantContext::load_by_code(&state, code).await?,
+        (None, None) => return Err(AppError::AuthenticationError("Tenant information is required".to_string())),
+    };
+    let tenant_id = tenant.tenant_id;
+
+    // 2. 拒绝已锁定或停用的账户；以事件存储中的用户状态为准，读模型可能尚未反映刚发生的锁定
+    let user_id = state.query_service
+        .get_user_by_username(&payload.username, tenant_id)
+        .await?
+        .map(|user| user.id);
+    if let Some(user_id) = user_id {
+        state.login_guard.check_user(user_id).await?;
+    }
+
+    // 3. 验证用户凭据
+    let user = match state.query_service
         .validate_user_credentials(&payload.username, &payload.password, tenant_id)
         .await?
-        .ok_or_else(|| AppError::AuthenticationError("Invalid username or password".to_string()))?;
-
-    // 4. 生成JWT token
-    let token = generate_token(
-        user.id,
-        user.username,
-        user.tenant_id,
-        &state.config.jwt.secret,
-        state.config.jwt.expiration_hours,
-    )?;
+    {
+        Some(user) => user,
+        None => {
+            // 记录失败次数，超过阈值时锁定账户；锁定事件写入事件存储供审计
+            state.login_guard.record_failure(user_id, &metadata).await?;
+            return Err(AppError::AuthenticationError("Invalid username or password".to_string()));
+        }
+    };
 
-    Ok((
-        StatusCode::OK,
-        Json(LoginResponse {
-            access_token: token,
-            token_type: "Bearer".to_string(),
-            expires_in: state.config.jwt.expiration_hours * 3600,
-        }),
-    ))
+    // 登录成功后清除该用户的失败计数
+    state.login_guard.record_success(user.id).await?;
+
+    // 签发新的刷新令牌家族
+    let refresh_token = state.refresh_tokens.issue(user.id, user.tenant_id, user.username).await?;
+
+    Ok((StatusCode::OK, Json(token_response(&state, refresh_token)?)))
 }
 
 /// 刷新token
@@ -92,34 +138,75 @@ pub async fn login(
     post,
     path = "/api/v1/auth/refresh",
     tag = "auth",
+    request_body = RefreshTokenRequest,
     responses(
         (status = 200, description = "令牌刷新成功", body = LoginResponse),
-        (status = 401, description = "无效的令牌"),
+        (status = 400, description = "请求参数错误"),
+        (status = 401, description = "刷新令牌无效、已过期、已吊销、被重复使用或租户已暂停"),
         (status = 500, description = "服务器内部错误")
     )
 )]
 pub async fn refresh_token(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshTokenRequest>,
 ) -> Result<Json<LoginResponse>, AppError> {
-    // TODO: 实现token刷新逻辑
-    Err(AppError::DomainError("Token refresh not implemented yet".to_string()))
+    payload.validate()?;
+
+    // 轮换刷新令牌；重复使用已轮换的令牌会吊销整个家族
+    let refresh_token = state.refresh_tokens.rotate(&payload.refresh_token).await?;
+
+    // 租户暂停后不再签发新的令牌
+    ensure_tenant_not_suspended(&state, refresh_token.tenant_id).await?;
+
+    Ok(Json(token_response(&state, refresh_token)?))
 }
 
-/// 用户登出
+/// 为刷新令牌的持有者生成访问令牌，组成登录和刷新的响应
+fn token_response(state: &AppState, refresh_token: IssuedRefreshToken) -> Result<LoginResponse, AppError> {
+    let access_token = generate_token(
+        refresh_token.user_id,
+        refresh_token.username,
+        refresh_token.tenant_id,
+        &state.key_ring,
+        state.config.jwt.expiration_hours,
+    )?;
+
+    Ok(LoginResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in: state.config.jwt.expiration_hours * 3600,
+        refresh_token: refresh_token.token,
+        refresh_expires_in: (refresh_token.expires_at - chrono::Utc::now()).num_seconds().max(0) as u64,
+    })
+}
+
+/// 用户登出，吊销当前访问令牌及请求中提供的刷新令牌
 #[utoipa::path(
     post,
     path = "/api/v1/auth/logout",
     tag = "auth",
+    request_body(content = LogoutRequest, description = "可选，提供刷新令牌时一并吊销"),
     responses(
         (status = 200, description = "登出成功"),
-        (status = 401, description = "未认证"),
+        (status = 401, description = "未认证或令牌已吊销"),
         (status = 500, description = "服务器内部错误")
     )
 )]
 pub async fn logout(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    payload: Option<Json<LogoutRequest>>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
-    // TODO: 实现登出逻辑（将token加入黑名单等）
+    // 吊销记录保留到令牌过期为止；没有jti的旧令牌只能通过登出所有会话吊销
+    match user.token_id.as_deref() {
+        Some(token_id) => state.token_revocations.revoke_token(token_id, user.expires_at).await?,
+        None => revoke_all_sessions(&state, &user).await?,
+    }
+
+    if let Some(refresh_token) = payload.and_then(|Json(payload)| payload.refresh_token) {
+        state.refresh_tokens.revoke(&refresh_token, user.user_id).await?;
+    }
+
     Ok((
         StatusCode::OK,
         Json(serde_json::json!({
@@ -127,3 +214,62 @@ pub async fn logout(
         })),
     ))
 }
+
+/// 登出所有会话，吊销当前用户此前签发的所有访问令牌和刷新令牌
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/logout-all",
+    tag = "auth",
+    responses(
+        (status = 200, description = "已登出所有会话"),
+        (status = 401, description = "未认证或令牌已吊销"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn logout_all(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    revoke_all_sessions(&state, &user).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "message": "Logged out of all sessions successfully"
+        })),
+    ))
+}
+
+/// 吊销用户到目前为止签发的所有令牌
+async fn revoke_all_sessions(state: &AppState, user: &AuthenticatedUser) -> Result<(), AppError> {
+    let now = chrono::Utc::now();
+    // 此刻之前签发的访问令牌最迟在一个有效期后全部过期，之后吊销记录即可清除
+    let expires_at = now + chrono::Duration::hours(state.config.jwt.expiration_hours as i64);
+
+    // 令牌的签发时间只精确到秒，按时间吊销只覆盖此前各秒签发的令牌，登出后同一秒内重新登录得到的令牌仍然有效；
+    // 当前令牌可能签发于这一秒内，单独按 jti 吊销
+    let issued_before = now.trunc_subsecs(0);
+    state.token_revocations.revoke_user_tokens(user.user_id, issued_before, expires_at).await?;
+    if let Some(token_id) = &user.token_id {
+        state.token_revocations.revoke_token(token_id, user.expires_at).await?;
+    }
+    state.refresh_tokens.revoke_all(user.user_id).await
+}
+
+/// 公开令牌签名公钥（JWKS），其他服务据此按 kid 验证访问令牌，无需持有签名密钥
+///
+/// 包含尚未开始签名的下一个密钥，验证方在密钥切换前即可缓存到它
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    tag = "auth",
+    responses(
+        (status = 200, description = "JSON Web Key Set", body = Object)
+    )
+)]
+pub async fn jwks(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        Json(state.key_ring.jwks()),
+    )
+}
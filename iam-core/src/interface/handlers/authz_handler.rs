@@ -0,0 +1,175 @@
+use axum::{extract::State, response::Json};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::services::AccessDecision;
+use crate::error::AppError;
+use crate::infrastructure::persistence::EffectivePermission;
+use crate::interface::middleware::auth::{is_platform_admin, AuthenticatedUser};
+use crate::interface::middleware::authorization::{AuthzCheck, RequirePermission};
+use crate::interface::middleware::AppState;
+
+#[derive(Debug, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CheckAccessRequest {
+    /// 要检查的用户ID
+    pub user_id: Uuid,
+    /// 访问发生的租户ID，必须是用户所属的租户
+    pub tenant_id: Uuid,
+    /// 资源，与操作组成权限代码 "资源:操作"，例如 user 与 read 对应 user:read；为空时操作即完整的权限代码
+    #[validate(length(min = 1, max = 100))]
+    pub resource: Option<String>,
+    /// 操作
+    #[validate(length(min = 1, max = 100))]
+    pub action: String,
+    /// 是否返回促成决策的全部角色和权限
+    #[serde(default)]
+    pub explain: bool,
+}
+
+impl CheckAccessRequest {
+    /// 检查的权限代码
+    fn permission_code(&self) -> String {
+        match &self.resource {
+            Some(resource) => format!("{}:{}", resource, self.action),
+            None => self.action.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct BatchCheckAccessRequest {
+    /// 要检查的访问，1-100个
+    #[validate(length(min = 1, max = 100))]
+    pub checks: Vec<CheckAccessRequest>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct GrantResponse {
+    /// 授予权限的角色ID
+    pub role_id: Uuid,
+    /// 检查的权限ID
+    pub permission_id: Uuid,
+    /// 授予角色的权限ID，通过上级菜单继承时为该菜单的ID
+    pub granted_permission_id: Uuid,
+    /// 是否通过上级菜单继承
+    pub inherited: bool,
+}
+
+impl From<&EffectivePermission> for GrantResponse {
+    fn from(grant: &EffectivePermission) -> Self {
+        Self {
+            role_id: grant.role_id,
+            permission_id: grant.permission_id,
+            granted_permission_id: grant.granted_permission_id,
+            inherited: grant.permission_id != grant.granted_permission_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CheckAccessResponse {
+    /// 是否允许
+    pub allowed: bool,
+    /// 检查的权限代码
+    pub permission: String,
+    /// 决策原因：granted、no_matching_grant、user_not_found、user_inactive、tenant_mismatch 或 tenant_inactive
+    pub reason: String,
+    /// 促成允许的授权，优先直接授予的权限
+    pub matched_grant: Option<GrantResponse>,
+    /// explain 模式下返回用户拥有该权限的全部授权
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grants: Option<Vec<GrantResponse>>,
+}
+
+impl CheckAccessResponse {
+    fn new(permission: String, decision: &AccessDecision, explain: bool) -> Self {
+        Self {
+            allowed: decision.is_allowed(),
+            permission,
+            reason: decision.reason.as_str().to_string(),
+            matched_grant: decision.matched_grant().map(GrantResponse::from),
+            grants: explain.then(|| decision.grants.iter().map(GrantResponse::from).collect()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BatchCheckAccessResponse {
+    /// 检查结果，顺序与请求一致
+    pub results: Vec<CheckAccessResponse>,
+}
+
+/// 只有平台管理员可以检查其他租户的访问
+async fn ensure_can_check(state: &AppState, caller: &AuthenticatedUser, tenant_id: Uuid) -> Result<(), AppError> {
+    if caller.tenant_id == tenant_id || is_platform_admin(state, caller).await? {
+        Ok(())
+    } else {
+        Err(AppError::AuthorizationError("Cannot check access in a different tenant".to_string()))
+    }
+}
+
+async fn check(state: &AppState, request: &CheckAccessRequest) -> Result<CheckAccessResponse, AppError> {
+    let permission = request.permission_code();
+    let decision = state.policy_decisions.check(request.user_id, request.tenant_id, &permission).await?;
+
+    Ok(CheckAccessResponse::new(permission, &decision, request.explain))
+}
+
+/// 检查用户能否在租户内执行资源上的操作，需要 authz:check 权限
+#[utoipa::path(
+    post,
+    path = "/api/v1/authz/check",
+    tag = "authz",
+    request_body = CheckAccessRequest,
+    responses(
+        (status = 200, description = "检查完成，allowed 表示是否允许", body = CheckAccessResponse),
+        (status = 400, description = "请求参数错误"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或不能检查其他租户"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn check_access(
+    State(state): State<AppState>,
+    guard: RequirePermission<AuthzCheck>,
+    Json(payload): Json<CheckAccessRequest>,
+) -> Result<Json<CheckAccessResponse>, AppError> {
+    payload.validate()?;
+    ensure_can_check(&state, &guard.user, payload.tenant_id).await?;
+
+    Ok(Json(check(&state, &payload).await?))
+}
+
+/// 批量检查访问，需要 authz:check 权限
+#[utoipa::path(
+    post,
+    path = "/api/v1/authz/check/batch",
+    tag = "authz",
+    request_body = BatchCheckAccessRequest,
+    responses(
+        (status = 200, description = "检查完成", body = BatchCheckAccessResponse),
+        (status = 400, description = "请求参数错误"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或不能检查其他租户"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn check_access_batch(
+    State(state): State<AppState>,
+    guard: RequirePermission<AuthzCheck>,
+    Json(payload): Json<BatchCheckAccessRequest>,
+) -> Result<Json<BatchCheckAccessResponse>, AppError> {
+    payload.validate()?;
+    for request in &payload.checks {
+        request.validate()?;
+        ensure_can_check(&state, &guard.user, request.tenant_id).await?;
+    }
+
+    let mut results = Vec::with_capacity(payload.checks.len());
+    for request in &payload.checks {
+        results.push(check(&state, request).await?);
+    }
+
+    Ok(Json(BatchCheckAccessResponse { results }))
+}
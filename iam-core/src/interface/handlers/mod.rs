@@ -1,5 +1,17 @@
 pub mod user_handler;
 pub mod auth_handler;
+pub mod admin_handler;
+pub mod role_handler;
+pub mod tenant_handler;
+pub mod organization_handler;
+pub mod permission_handler;
+pub mod authz_handler;
 
 pub use user_handler::*;
 pub use auth_handler::*;
+pub use admin_handler::*;
+pub use role_handler::*;
+pub use tenant_handler::*;
+pub use organization_handler::*;
+pub use permission_handler::*;
+pub use authz_handler::*;
@@ -0,0 +1,474 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::dtos::organization_view;
+use crate::domain::identity_access::commands::{
+    AssignUserOrganizationCommand, CreateOrganizationCommand, DeleteOrganizationCommand, MoveOrganizationCommand,
+    RemoveUserOrganizationCommand, RenameOrganizationCommand,
+};
+use crate::error::AppError;
+use crate::infrastructure::persistence::event_store::EventMetadata;
+use crate::interface::handlers::user_handler::UserResponse;
+use crate::interface::middleware::{
+    authorization::{OrganizationAssign, OrganizationRead, OrganizationWrite, RequirePermission},
+    tenant::TenantContext,
+    AppState,
+};
+
+/// 单次查询返回的最大成员数
+const MAX_PAGE_SIZE: u64 = 100;
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateOrganizationRequest {
+    /// 上级组织ID，为空时创建根组织
+    pub parent_id: Option<Uuid>,
+    /// 组织名称，1-255个字符
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    /// 组织代码，1-100个字符，只能包含字母、数字、下划线和连字符
+    #[validate(length(min = 1, max = 100))]
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CreateOrganizationResponse {
+    /// 组织ID
+    pub organization_id: Uuid,
+    /// 响应消息
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct RenameOrganizationRequest {
+    /// 组织名称，1-255个字符
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct MoveOrganizationRequest {
+    /// 新的上级组织ID，为空时移动为根组织
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AssignUserOrganizationRequest {
+    /// 用户ID
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListMembersQuery {
+    /// 是否包含所有下级组织的成员
+    #[serde(default)]
+    pub include_descendants: bool,
+    /// 返回数量，默认且最多100
+    pub limit: Option<u64>,
+    /// 偏移量
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct OrganizationNode {
+    /// 组织ID
+    pub id: Uuid,
+    /// 上级组织ID
+    pub parent_id: Option<Uuid>,
+    /// 组织名称
+    pub name: String,
+    /// 组织代码
+    pub code: String,
+    /// 层级，根组织为0
+    pub level: i32,
+    /// 下级组织
+    pub children: Vec<OrganizationNode>,
+    /// 创建时间
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// 更新时间
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl OrganizationNode {
+    /// 把按路径排序的组织列表组装成树，上级不在列表中的组织作为根
+    pub(crate) fn build_tree(organizations: Vec<organization_view::Model>) -> Vec<OrganizationNode> {
+        let ids: std::collections::HashSet<Uuid> = organizations.iter().map(|organization| organization.id).collect();
+        let mut children: HashMap<Option<Uuid>, Vec<organization_view::Model>> = HashMap::new();
+        for organization in organizations {
+            let parent_id = organization.parent_id.filter(|parent_id| ids.contains(parent_id));
+            children.entry(parent_id).or_default().push(organization);
+        }
+
+        Self::build_level(None, &mut children)
+    }
+
+    fn build_level(
+        parent_id: Option<Uuid>,
+        children: &mut HashMap<Option<Uuid>, Vec<organization_view::Model>>,
+    ) -> Vec<OrganizationNode> {
+        children
+            .remove(&parent_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|organization| OrganizationNode {
+                children: Self::build_level(Some(organization.id), children),
+                id: organization.id,
+                parent_id: organization.parent_id,
+                name: organization.name,
+                code: organization.code,
+                level: organization.level,
+                created_at: organization.created_at,
+                updated_at: organization.updated_at,
+            })
+            .collect()
+    }
+}
+
+/// 查询租户下的组织，不存在时返回 404
+async fn find_organization(
+    state: &AppState,
+    tenant: &TenantContext,
+    organization_id: Uuid,
+) -> Result<organization_view::Model, AppError> {
+    state.query_service
+        .get_organization_by_id(organization_id, tenant.tenant_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Organization with ID {} not found", organization_id)))
+}
+
+/// 获取租户的组织树，需要 organization:read 权限
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations",
+    tag = "organizations",
+    responses(
+        (status = 200, description = "获取组织树成功", body = Vec<OrganizationNode>),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或租户未启用"),
+        (status = 404, description = "租户不存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn get_organization_tree(
+    State(state): State<AppState>,
+    _guard: RequirePermission<OrganizationRead>,
+    tenant: TenantContext,
+) -> Result<Json<Vec<OrganizationNode>>, AppError> {
+    let organizations = state.query_service.get_organizations_by_tenant(tenant.tenant_id).await?;
+
+    Ok(Json(OrganizationNode::build_tree(organizations)))
+}
+
+/// 获取以指定组织为根的子树，需要 organization:read 权限
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{organization_id}",
+    tag = "organizations",
+    params(
+        ("organization_id" = Uuid, Path, description = "组织ID")
+    ),
+    responses(
+        (status = 200, description = "获取组织子树成功", body = OrganizationNode),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "组织不存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn get_organization_subtree(
+    State(state): State<AppState>,
+    _guard: RequirePermission<OrganizationRead>,
+    tenant: TenantContext,
+    Path(organization_id): Path<Uuid>,
+) -> Result<Json<OrganizationNode>, AppError> {
+    let organization = find_organization(&state, &tenant, organization_id).await?;
+    let subtree = state.query_service.get_organization_subtree(&organization).await?;
+
+    OrganizationNode::build_tree(subtree)
+        .into_iter()
+        .next()
+        .map(Json)
+        .ok_or_else(|| AppError::NotFound(format!("Organization with ID {} not found", organization_id)))
+}
+
+/// 创建组织，需要 organization:write 权限
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations",
+    tag = "organizations",
+    request_body = CreateOrganizationRequest,
+    responses(
+        (status = 201, description = "组织创建成功", body = CreateOrganizationResponse),
+        (status = 400, description = "请求参数错误或上级组织已删除"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "上级组织不存在"),
+        (status = 409, description = "组织代码已存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn create_organization(
+    State(state): State<AppState>,
+    _guard: RequirePermission<OrganizationWrite>,
+    tenant: TenantContext,
+    metadata: EventMetadata,
+    Json(payload): Json<CreateOrganizationRequest>,
+) -> Result<(StatusCode, Json<CreateOrganizationResponse>), AppError> {
+    payload.validate()?;
+
+    // 读模型最终一致，这里只能拦截已投影的重复代码
+    if state.query_service.get_organization_by_code(&payload.code, tenant.tenant_id).await?.is_some() {
+        return Err(AppError::Conflict(format!("Organization with code {} already exists", payload.code)));
+    }
+
+    let command = CreateOrganizationCommand {
+        tenant_id: tenant.tenant_id,
+        parent_id: payload.parent_id,
+        name: payload.name,
+        code: payload.code,
+    };
+    let organization_id = state.organization_service.create_organization(command, &metadata).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateOrganizationResponse {
+            organization_id,
+            message: "Organization created successfully".to_string(),
+        }),
+    ))
+}
+
+/// 修改组织名称，需要 organization:write 权限
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/{organization_id}",
+    tag = "organizations",
+    params(
+        ("organization_id" = Uuid, Path, description = "组织ID")
+    ),
+    request_body = RenameOrganizationRequest,
+    responses(
+        (status = 204, description = "组织更新成功"),
+        (status = 400, description = "请求参数错误或组织已删除"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "组织不存在"),
+        (status = 409, description = "组织已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn rename_organization(
+    State(state): State<AppState>,
+    _guard: RequirePermission<OrganizationWrite>,
+    tenant: TenantContext,
+    Path(organization_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<RenameOrganizationRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+
+    let command = RenameOrganizationCommand {
+        tenant_id: tenant.tenant_id,
+        organization_id,
+        name: payload.name,
+    };
+    state.organization_service.rename_organization(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 把组织连同其下级组织移动到新的上级组织下，需要 organization:write 权限
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/{organization_id}/move",
+    tag = "organizations",
+    params(
+        ("organization_id" = Uuid, Path, description = "组织ID")
+    ),
+    request_body = MoveOrganizationRequest,
+    responses(
+        (status = 204, description = "组织移动成功"),
+        (status = 400, description = "目标为自身或下级组织、组织已删除或已在该上级组织下"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "组织或上级组织不存在"),
+        (status = 409, description = "组织已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn move_organization(
+    State(state): State<AppState>,
+    _guard: RequirePermission<OrganizationWrite>,
+    tenant: TenantContext,
+    Path(organization_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<MoveOrganizationRequest>,
+) -> Result<StatusCode, AppError> {
+    let command = MoveOrganizationCommand {
+        tenant_id: tenant.tenant_id,
+        organization_id,
+        parent_id: payload.parent_id,
+    };
+    state.organization_service.move_organization(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 删除组织，组织下不能再有下级组织，需要 organization:write 权限
+#[utoipa::path(
+    delete,
+    path = "/api/v1/organizations/{organization_id}",
+    tag = "organizations",
+    params(
+        ("organization_id" = Uuid, Path, description = "组织ID")
+    ),
+    responses(
+        (status = 204, description = "组织删除成功"),
+        (status = 400, description = "组织已删除或仍有下级组织"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "组织不存在"),
+        (status = 409, description = "组织已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn delete_organization(
+    State(state): State<AppState>,
+    _guard: RequirePermission<OrganizationWrite>,
+    tenant: TenantContext,
+    Path(organization_id): Path<Uuid>,
+    metadata: EventMetadata,
+) -> Result<StatusCode, AppError> {
+    let command = DeleteOrganizationCommand {
+        tenant_id: tenant.tenant_id,
+        organization_id,
+    };
+    state.organization_service.delete_organization(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 获取组织成员，需要 organization:read 权限
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{organization_id}/members",
+    tag = "organizations",
+    params(
+        ("organization_id" = Uuid, Path, description = "组织ID"),
+        ListMembersQuery
+    ),
+    responses(
+        (status = 200, description = "获取组织成员成功", body = Vec<UserResponse>),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "组织不存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn list_organization_members(
+    State(state): State<AppState>,
+    _guard: RequirePermission<OrganizationRead>,
+    tenant: TenantContext,
+    Path(organization_id): Path<Uuid>,
+    Query(query): Query<ListMembersQuery>,
+) -> Result<Json<Vec<UserResponse>>, AppError> {
+    let organization = find_organization(&state, &tenant, organization_id).await?;
+    let organization_ids = if query.include_descendants {
+        state.query_service
+            .get_organization_subtree(&organization)
+            .await?
+            .into_iter()
+            .map(|organization| organization.id)
+            .collect()
+    } else {
+        vec![organization.id]
+    };
+
+    let limit = Some(query.limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE));
+    let users = state.query_service
+        .get_organization_members(organization_ids, limit, query.offset)
+        .await?;
+
+    Ok(Json(users.into_iter().map(UserResponse::from).collect()))
+}
+
+/// 把用户加入组织，需要 organization:assign 权限
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/{organization_id}/members",
+    tag = "organizations",
+    params(
+        ("organization_id" = Uuid, Path, description = "组织ID")
+    ),
+    request_body = AssignUserOrganizationRequest,
+    responses(
+        (status = 204, description = "用户加入组织成功"),
+        (status = 400, description = "用户已在组织中、用户已停用、组织已删除或属于其他租户"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "用户或组织不存在"),
+        (status = 409, description = "用户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn assign_user_organization(
+    State(state): State<AppState>,
+    _guard: RequirePermission<OrganizationAssign>,
+    tenant: TenantContext,
+    Path(organization_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<AssignUserOrganizationRequest>,
+) -> Result<StatusCode, AppError> {
+    let command = AssignUserOrganizationCommand {
+        tenant_id: tenant.tenant_id,
+        user_id: payload.user_id,
+        organization_id,
+    };
+    state.organization_service.assign_user_organization(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 把用户移出组织，需要 organization:assign 权限
+#[utoipa::path(
+    delete,
+    path = "/api/v1/organizations/{organization_id}/members/{user_id}",
+    tag = "organizations",
+    params(
+        ("organization_id" = Uuid, Path, description = "组织ID"),
+        ("user_id" = Uuid, Path, description = "用户ID")
+    ),
+    responses(
+        (status = 204, description = "用户移出组织成功"),
+        (status = 400, description = "用户不在该组织中"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "用户或组织不存在"),
+        (status = 409, description = "用户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn remove_user_organization(
+    State(state): State<AppState>,
+    _guard: RequirePermission<OrganizationAssign>,
+    tenant: TenantContext,
+    Path((organization_id, user_id)): Path<(Uuid, Uuid)>,
+    metadata: EventMetadata,
+) -> Result<StatusCode, AppError> {
+    let command = RemoveUserOrganizationCommand {
+        tenant_id: tenant.tenant_id,
+        user_id,
+        organization_id,
+    };
+    state.organization_service.remove_user_organization(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
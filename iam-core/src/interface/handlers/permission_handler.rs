@@ -0,0 +1,317 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::dtos::permission_view;
+use crate::domain::identity_access::commands::{
+    CreatePermissionCommand, DeletePermissionCommand, UpdatePermissionCommand,
+};
+use crate::error::AppError;
+use crate::infrastructure::persistence::event_store::EventMetadata;
+use crate::interface::middleware::{
+    auth::PlatformAdmin,
+    authorization::{PermissionRead, RequirePermission},
+    AppState,
+};
+
+/// 单次查询返回的最大权限数
+const MAX_PAGE_SIZE: u64 = 100;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListPermissionsQuery {
+    /// 按类型过滤：menu、button 或 api
+    #[serde(rename = "type")]
+    #[param(rename = "type")]
+    pub permission_type: Option<String>,
+    /// 是否包含已删除的权限
+    #[serde(default)]
+    pub include_deleted: bool,
+    /// 返回数量，默认且最多100
+    pub limit: Option<u64>,
+    /// 偏移量
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreatePermissionRequest {
+    /// 上级菜单ID，为空时创建顶级权限
+    pub parent_id: Option<Uuid>,
+    /// 权限类型：menu、button 或 api
+    #[serde(rename = "type")]
+    #[schema(rename = "type")]
+    pub permission_type: String,
+    /// 权限名称，1-100个字符
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// 权限代码，1-100个字符，只能包含字母、数字和 : _ - .，例如 user:read
+    #[validate(length(min = 1, max = 100))]
+    pub code: String,
+    /// 菜单为页面路径；API 为 "方法 路径"，例如 "GET /api/v1/users/:id"；按钮没有路由
+    #[validate(length(min = 1, max = 1024))]
+    pub route: Option<String>,
+    /// 菜单图标
+    #[validate(length(max = 255))]
+    pub icon: Option<String>,
+    /// 权限描述
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CreatePermissionResponse {
+    /// 权限ID
+    pub permission_id: Uuid,
+    /// 响应消息
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct UpdatePermissionRequest {
+    /// 权限名称，1-100个字符
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    /// 路由，规则与创建时相同
+    #[validate(length(min = 1, max = 1024))]
+    pub route: Option<String>,
+    /// 菜单图标
+    #[validate(length(max = 255))]
+    pub icon: Option<String>,
+    /// 权限描述
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PermissionResponse {
+    /// 权限ID
+    pub id: Uuid,
+    /// 上级菜单ID
+    pub parent_id: Option<Uuid>,
+    /// 权限类型：menu、button 或 api
+    #[serde(rename = "type")]
+    #[schema(rename = "type")]
+    pub permission_type: String,
+    /// 权限名称
+    pub name: String,
+    /// 权限代码
+    pub code: String,
+    /// 路由
+    pub route: Option<String>,
+    /// 菜单图标
+    pub icon: Option<String>,
+    /// 权限描述
+    pub description: Option<String>,
+    /// 是否已删除
+    pub deleted: bool,
+    /// 创建时间
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// 更新时间
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// 删除时间
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<permission_view::Model> for PermissionResponse {
+    fn from(permission: permission_view::Model) -> Self {
+        Self {
+            id: permission.id,
+            parent_id: permission.parent_id,
+            permission_type: permission.permission_type,
+            name: permission.name,
+            code: permission.code,
+            route: permission.route,
+            icon: permission.icon,
+            description: permission.description,
+            deleted: permission.deleted,
+            created_at: permission.created_at,
+            updated_at: permission.updated_at,
+            deleted_at: permission.deleted_at,
+        }
+    }
+}
+
+/// 获取权限目录，需要 permission:read 权限
+#[utoipa::path(
+    get,
+    path = "/api/v1/permissions",
+    tag = "permissions",
+    params(ListPermissionsQuery),
+    responses(
+        (status = 200, description = "获取权限列表成功", body = Vec<PermissionResponse>),
+        (status = 400, description = "请求参数错误"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn list_permissions(
+    State(state): State<AppState>,
+    _guard: RequirePermission<PermissionRead>,
+    Query(query): Query<ListPermissionsQuery>,
+) -> Result<Json<Vec<PermissionResponse>>, AppError> {
+    let limit = Some(query.limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE));
+
+    let permissions = state.query_service
+        .get_permissions(query.permission_type.as_deref(), query.include_deleted, limit, query.offset)
+        .await?;
+
+    Ok(Json(permissions.into_iter().map(PermissionResponse::from).collect()))
+}
+
+/// 根据ID获取权限信息，已删除的权限同样返回，需要 permission:read 权限
+#[utoipa::path(
+    get,
+    path = "/api/v1/permissions/{permission_id}",
+    tag = "permissions",
+    params(
+        ("permission_id" = Uuid, Path, description = "权限ID")
+    ),
+    responses(
+        (status = 200, description = "获取权限信息成功", body = PermissionResponse),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "权限不存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn get_permission(
+    State(state): State<AppState>,
+    _guard: RequirePermission<PermissionRead>,
+    Path(permission_id): Path<Uuid>,
+) -> Result<Json<PermissionResponse>, AppError> {
+    let permission = state.query_service
+        .get_permission_by_id(permission_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Permission with ID {} not found", permission_id)))?;
+
+    Ok(Json(permission.into()))
+}
+
+/// 创建权限
+#[utoipa::path(
+    post,
+    path = "/api/v1/permissions",
+    tag = "permissions",
+    request_body = CreatePermissionRequest,
+    responses(
+        (status = 201, description = "权限创建成功", body = CreatePermissionResponse),
+        (status = 400, description = "请求参数错误、类型与路由不符或上级权限不是菜单"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限"),
+        (status = 404, description = "上级权限不存在"),
+        (status = 409, description = "权限代码已存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn create_permission(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+    metadata: EventMetadata,
+    Json(payload): Json<CreatePermissionRequest>,
+) -> Result<(StatusCode, Json<CreatePermissionResponse>), AppError> {
+    payload.validate()?;
+
+    // 读模型最终一致，这里只能拦截已投影的重复代码
+    if state.query_service.get_permission_by_code(&payload.code).await?.is_some() {
+        return Err(AppError::Conflict(format!("Permission with code {} already exists", payload.code)));
+    }
+
+    let command = CreatePermissionCommand {
+        parent_id: payload.parent_id,
+        permission_type: payload.permission_type,
+        name: payload.name,
+        code: payload.code,
+        route: payload.route,
+        icon: payload.icon,
+        description: payload.description,
+    };
+    let permission_id = state.permission_service.create_permission(command, &metadata).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatePermissionResponse {
+            permission_id,
+            message: "Permission created successfully".to_string(),
+        }),
+    ))
+}
+
+/// 更新权限，类型、代码和上级权限不可修改
+#[utoipa::path(
+    put,
+    path = "/api/v1/permissions/{permission_id}",
+    tag = "permissions",
+    params(
+        ("permission_id" = Uuid, Path, description = "权限ID")
+    ),
+    request_body = UpdatePermissionRequest,
+    responses(
+        (status = 204, description = "权限更新成功"),
+        (status = 400, description = "请求参数错误或权限已删除"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限"),
+        (status = 404, description = "权限不存在"),
+        (status = 409, description = "权限已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn update_permission(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+    Path(permission_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<UpdatePermissionRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+
+    let command = UpdatePermissionCommand {
+        permission_id,
+        name: payload.name,
+        route: payload.route,
+        icon: payload.icon,
+        description: payload.description,
+    };
+    state.permission_service.update_permission(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 删除权限，权限保留并标记为已删除，同时不再授予任何角色
+#[utoipa::path(
+    delete,
+    path = "/api/v1/permissions/{permission_id}",
+    tag = "permissions",
+    params(
+        ("permission_id" = Uuid, Path, description = "权限ID")
+    ),
+    responses(
+        (status = 204, description = "权限删除成功"),
+        (status = 400, description = "权限已删除或仍有下级权限"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限"),
+        (status = 404, description = "权限不存在"),
+        (status = 409, description = "权限已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn delete_permission(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+    Path(permission_id): Path<Uuid>,
+    metadata: EventMetadata,
+) -> Result<StatusCode, AppError> {
+    // 读模型最终一致，这里只能拦截已投影的下级权限
+    if state.query_service.has_child_permissions(permission_id).await? {
+        return Err(AppError::DomainError("Cannot delete a permission that has child permissions".to_string()));
+    }
+
+    state.permission_service.delete_permission(DeletePermissionCommand { permission_id }, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
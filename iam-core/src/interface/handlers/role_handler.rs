@@ -0,0 +1,526 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::application::dtos::role_view;
+use crate::domain::identity_access::commands::{
+    AssignUserRoleCommand, CreateRoleCommand, DeleteRoleCommand, GrantRolePermissionCommand, RemoveUserRoleCommand,
+    RevokeRolePermissionCommand, UpdateRoleCommand,
+};
+use crate::error::AppError;
+use crate::infrastructure::persistence::event_store::EventMetadata;
+use crate::interface::handlers::permission_handler::PermissionResponse;
+use crate::interface::handlers::user_handler::ensure_same_tenant;
+use crate::interface::middleware::authorization::{RequirePermission, RoleAssign, RoleGrant, RoleRead, RoleWrite};
+use crate::interface::middleware::tenant::TenantContext;
+use crate::interface::middleware::AppState;
+
+/// 单次查询返回的最大角色数
+const MAX_PAGE_SIZE: u64 = 100;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListRolesQuery {
+    /// 按名称或代码模糊搜索
+    pub q: Option<String>,
+    /// 是否包含已删除的角色，搜索时忽略
+    #[serde(default)]
+    pub include_deleted: bool,
+    /// 返回数量，默认且最多100
+    pub limit: Option<u64>,
+    /// 偏移量
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateRoleRequest {
+    /// 角色名称，1-100个字符
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// 角色代码，1-100个字符，只能包含字母、数字和下划线
+    #[validate(length(min = 1, max = 100), custom(function = "validate_role_code"))]
+    pub code: String,
+    /// 角色描述
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+}
+
+/// 角色代码只能包含字母、数字和下划线
+fn validate_role_code(code: &str) -> Result<(), ValidationError> {
+    if code.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        Ok(())
+    } else {
+        Err(ValidationError::new("role_code"))
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CreateRoleResponse {
+    /// 角色ID
+    pub role_id: Uuid,
+    /// 响应消息
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct UpdateRoleRequest {
+    /// 角色名称，1-100个字符
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    /// 角色描述
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AssignUserRoleRequest {
+    /// 角色ID
+    pub role_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct GrantRolePermissionRequest {
+    /// 权限ID
+    pub permission_id: Uuid,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RoleResponse {
+    /// 角色ID
+    pub id: Uuid,
+    /// 租户ID
+    pub tenant_id: Uuid,
+    /// 角色名称
+    pub name: String,
+    /// 角色代码
+    pub code: String,
+    /// 角色描述
+    pub description: Option<String>,
+    /// 是否已删除
+    pub deleted: bool,
+    /// 创建时间
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// 更新时间
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// 删除时间
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<role_view::Model> for RoleResponse {
+    fn from(role: role_view::Model) -> Self {
+        Self {
+            id: role.id,
+            tenant_id: role.tenant_id,
+            name: role.name,
+            code: role.code,
+            description: role.description,
+            deleted: role.deleted,
+            created_at: role.created_at,
+            updated_at: role.updated_at,
+            deleted_at: role.deleted_at,
+        }
+    }
+}
+
+/// 获取或搜索本租户的角色列表，需要 role:read 权限
+#[utoipa::path(
+    get,
+    path = "/api/v1/roles",
+    tag = "roles",
+    params(ListRolesQuery),
+    responses(
+        (status = 200, description = "获取角色列表成功", body = Vec<RoleResponse>),
+        (status = 400, description = "请求参数错误"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn list_roles(
+    State(state): State<AppState>,
+    _guard: RequirePermission<RoleRead>,
+    tenant: TenantContext,
+    Query(query): Query<ListRolesQuery>,
+) -> Result<Json<Vec<RoleResponse>>, AppError> {
+    let limit = Some(query.limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE));
+
+    let roles = match query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        Some(keyword) => state.query_service
+            .search_roles(keyword, tenant.tenant_id, limit, query.offset)
+            .await?,
+        None => state.query_service
+            .get_roles_by_tenant(tenant.tenant_id, query.include_deleted, limit, query.offset)
+            .await?,
+    };
+
+    Ok(Json(roles.into_iter().map(RoleResponse::from).collect()))
+}
+
+/// 根据ID获取本租户的角色信息，已删除的角色同样返回，需要 role:read 权限
+#[utoipa::path(
+    get,
+    path = "/api/v1/roles/{role_id}",
+    tag = "roles",
+    params(
+        ("role_id" = Uuid, Path, description = "角色ID")
+    ),
+    responses(
+        (status = 200, description = "获取角色信息成功", body = RoleResponse),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "角色不存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn get_role(
+    State(state): State<AppState>,
+    _guard: RequirePermission<RoleRead>,
+    tenant: TenantContext,
+    Path(role_id): Path<Uuid>,
+) -> Result<Json<RoleResponse>, AppError> {
+    let role = state.query_service
+        .get_role_by_id(role_id)
+        .await?
+        .filter(|role| role.tenant_id == tenant.tenant_id)
+        .ok_or_else(|| AppError::NotFound(format!("Role with ID {} not found", role_id)))?;
+
+    Ok(Json(role.into()))
+}
+
+/// 根据代码获取本租户未删除的角色，需要 role:read 权限
+#[utoipa::path(
+    get,
+    path = "/api/v1/roles/code/{code}",
+    tag = "roles",
+    params(
+        ("code" = String, Path, description = "角色代码")
+    ),
+    responses(
+        (status = 200, description = "获取角色信息成功", body = RoleResponse),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "角色不存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn get_role_by_code(
+    State(state): State<AppState>,
+    _guard: RequirePermission<RoleRead>,
+    tenant: TenantContext,
+    Path(code): Path<String>,
+) -> Result<Json<RoleResponse>, AppError> {
+    let role = state.query_service
+        .get_role_by_code(&code, tenant.tenant_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Role with code {} not found", code)))?;
+
+    Ok(Json(role.into()))
+}
+
+/// 在本租户创建角色，需要 role:write 权限
+#[utoipa::path(
+    post,
+    path = "/api/v1/roles",
+    tag = "roles",
+    request_body = CreateRoleRequest,
+    responses(
+        (status = 201, description = "角色创建成功", body = CreateRoleResponse),
+        (status = 400, description = "请求参数错误"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 409, description = "角色代码已存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn create_role(
+    State(state): State<AppState>,
+    _guard: RequirePermission<RoleWrite>,
+    tenant: TenantContext,
+    metadata: EventMetadata,
+    Json(payload): Json<CreateRoleRequest>,
+) -> Result<(StatusCode, Json<CreateRoleResponse>), AppError> {
+    // 验证输入
+    payload.validate()?;
+
+    // 读模型最终一致，这里只能拦截已投影的重复代码
+    if state.query_service.get_role_by_code(&payload.code, tenant.tenant_id).await?.is_some() {
+        return Err(AppError::Conflict(format!("Role with code {} already exists", payload.code)));
+    }
+
+    // 创建命令
+    let command = CreateRoleCommand {
+        tenant_id: tenant.tenant_id,
+        name: payload.name,
+        code: payload.code,
+        description: payload.description,
+    };
+
+    // 执行命令
+    let role_id = state.role_service.create_role(command, &metadata).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateRoleResponse {
+            role_id,
+            message: "Role created successfully".to_string(),
+        }),
+    ))
+}
+
+/// 更新本租户的角色，需要 role:write 权限
+#[utoipa::path(
+    put,
+    path = "/api/v1/roles/{role_id}",
+    tag = "roles",
+    params(
+        ("role_id" = Uuid, Path, description = "角色ID")
+    ),
+    request_body = UpdateRoleRequest,
+    responses(
+        (status = 204, description = "角色更新成功"),
+        (status = 400, description = "请求参数错误或角色已删除"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或角色属于其他租户"),
+        (status = 404, description = "角色不存在"),
+        (status = 409, description = "角色已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn update_role(
+    State(state): State<AppState>,
+    _guard: RequirePermission<RoleWrite>,
+    tenant: TenantContext,
+    Path(role_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<UpdateRoleRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+    ensure_role_in_tenant(&state, tenant.tenant_id, role_id).await?;
+
+    let command = UpdateRoleCommand {
+        role_id,
+        name: payload.name,
+        description: payload.description,
+    };
+    state.role_service.update_role(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 删除本租户的角色，角色保留并标记为已删除，需要 role:write 权限
+#[utoipa::path(
+    delete,
+    path = "/api/v1/roles/{role_id}",
+    tag = "roles",
+    params(
+        ("role_id" = Uuid, Path, description = "角色ID")
+    ),
+    responses(
+        (status = 204, description = "角色删除成功"),
+        (status = 400, description = "角色已删除"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或角色属于其他租户"),
+        (status = 404, description = "角色不存在"),
+        (status = 409, description = "角色已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn delete_role(
+    State(state): State<AppState>,
+    _guard: RequirePermission<RoleWrite>,
+    tenant: TenantContext,
+    Path(role_id): Path<Uuid>,
+    metadata: EventMetadata,
+) -> Result<StatusCode, AppError> {
+    ensure_role_in_tenant(&state, tenant.tenant_id, role_id).await?;
+    state.role_service.delete_role(DeleteRoleCommand { role_id }, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 为本租户的用户分配角色，需要 role:assign 权限
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{user_id}/roles",
+    tag = "roles",
+    params(
+        ("user_id" = Uuid, Path, description = "用户ID")
+    ),
+    request_body = AssignUserRoleRequest,
+    responses(
+        (status = 204, description = "角色分配成功"),
+        (status = 400, description = "用户已拥有该角色、用户已停用、角色已删除或属于其他租户"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或用户属于其他租户"),
+        (status = 404, description = "用户或角色不存在"),
+        (status = 409, description = "用户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn assign_user_role(
+    State(state): State<AppState>,
+    _guard: RequirePermission<RoleAssign>,
+    tenant: TenantContext,
+    Path(user_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<AssignUserRoleRequest>,
+) -> Result<StatusCode, AppError> {
+    // 角色服务确认角色与用户属于同一租户
+    ensure_same_tenant(&state, tenant.tenant_id, user_id).await?;
+
+    let command = AssignUserRoleCommand {
+        user_id,
+        role_id: payload.role_id,
+    };
+    state.role_service.assign_user_role(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 移除本租户用户的角色，需要 role:assign 权限
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{user_id}/roles/{role_id}",
+    tag = "roles",
+    params(
+        ("user_id" = Uuid, Path, description = "用户ID"),
+        ("role_id" = Uuid, Path, description = "角色ID")
+    ),
+    responses(
+        (status = 204, description = "角色移除成功"),
+        (status = 400, description = "用户未拥有该角色"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或用户属于其他租户"),
+        (status = 404, description = "用户不存在"),
+        (status = 409, description = "用户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn remove_user_role(
+    State(state): State<AppState>,
+    _guard: RequirePermission<RoleAssign>,
+    tenant: TenantContext,
+    Path((user_id, role_id)): Path<(Uuid, Uuid)>,
+    metadata: EventMetadata,
+) -> Result<StatusCode, AppError> {
+    ensure_same_tenant(&state, tenant.tenant_id, user_id).await?;
+    state.role_service.remove_user_role(RemoveUserRoleCommand { user_id, role_id }, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 获取授予本租户角色的权限，需要 role:read 权限
+#[utoipa::path(
+    get,
+    path = "/api/v1/roles/{role_id}/permissions",
+    tag = "roles",
+    params(
+        ("role_id" = Uuid, Path, description = "角色ID")
+    ),
+    responses(
+        (status = 200, description = "获取角色权限成功", body = Vec<PermissionResponse>),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限"),
+        (status = 404, description = "角色不存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn list_role_permissions(
+    State(state): State<AppState>,
+    _guard: RequirePermission<RoleRead>,
+    tenant: TenantContext,
+    Path(role_id): Path<Uuid>,
+) -> Result<Json<Vec<PermissionResponse>>, AppError> {
+    state.query_service
+        .get_role_by_id(role_id)
+        .await?
+        .filter(|role| role.tenant_id == tenant.tenant_id)
+        .ok_or_else(|| AppError::NotFound(format!("Role with ID {} not found", role_id)))?;
+
+    let permissions = state.query_service.get_role_permissions(role_id).await?;
+
+    Ok(Json(permissions.into_iter().map(PermissionResponse::from).collect()))
+}
+
+/// 为本租户的角色授予权限，需要 role:grant 权限
+#[utoipa::path(
+    post,
+    path = "/api/v1/roles/{role_id}/permissions",
+    tag = "roles",
+    params(
+        ("role_id" = Uuid, Path, description = "角色ID")
+    ),
+    request_body = GrantRolePermissionRequest,
+    responses(
+        (status = 204, description = "权限授予成功"),
+        (status = 400, description = "角色已拥有该权限、角色已删除或权限已删除"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或角色属于其他租户"),
+        (status = 404, description = "角色或权限不存在"),
+        (status = 409, description = "角色已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn grant_role_permission(
+    State(state): State<AppState>,
+    _guard: RequirePermission<RoleGrant>,
+    tenant: TenantContext,
+    Path(role_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<GrantRolePermissionRequest>,
+) -> Result<StatusCode, AppError> {
+    ensure_role_in_tenant(&state, tenant.tenant_id, role_id).await?;
+    let command = GrantRolePermissionCommand {
+        role_id,
+        permission_id: payload.permission_id,
+    };
+    state.role_service.grant_role_permission(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 撤销本租户角色的权限，需要 role:grant 权限
+#[utoipa::path(
+    delete,
+    path = "/api/v1/roles/{role_id}/permissions/{permission_id}",
+    tag = "roles",
+    params(
+        ("role_id" = Uuid, Path, description = "角色ID"),
+        ("permission_id" = Uuid, Path, description = "权限ID")
+    ),
+    responses(
+        (status = 204, description = "权限撤销成功"),
+        (status = 400, description = "角色未拥有该权限"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或角色属于其他租户"),
+        (status = 404, description = "角色不存在"),
+        (status = 409, description = "角色已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn revoke_role_permission(
+    State(state): State<AppState>,
+    _guard: RequirePermission<RoleGrant>,
+    tenant: TenantContext,
+    Path((role_id, permission_id)): Path<(Uuid, Uuid)>,
+    metadata: EventMetadata,
+) -> Result<StatusCode, AppError> {
+    ensure_role_in_tenant(&state, tenant.tenant_id, role_id).await?;
+    state.role_service.revoke_role_permission(RevokeRolePermissionCommand { role_id, permission_id }, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 确认角色属于指定租户
+async fn ensure_role_in_tenant(state: &AppState, tenant_id: Uuid, role_id: Uuid) -> Result<(), AppError> {
+    let role = state.role_service.get_role(role_id).await?;
+    if role.tenant_id() != tenant_id {
+        return Err(AppError::AuthorizationError("Role belongs to a different tenant".to_string()));
+    }
+    Ok(())
+}
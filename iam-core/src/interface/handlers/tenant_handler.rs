@@ -0,0 +1,309 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::dtos::tenant_view;
+use crate::domain::identity_access::commands::{
+    CreateTenantCommand, ReactivateTenantCommand, RenameTenantCommand, SuspendTenantCommand,
+};
+use crate::error::AppError;
+use crate::infrastructure::persistence::event_store::EventMetadata;
+use crate::interface::middleware::{auth::PlatformAdmin, AppState};
+
+/// 单次查询返回的最大租户数
+const MAX_PAGE_SIZE: u64 = 100;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListTenantsQuery {
+    /// 按状态过滤：active 或 suspended
+    pub status: Option<String>,
+    /// 返回数量，默认且最多100
+    pub limit: Option<u64>,
+    /// 偏移量
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateTenantRequest {
+    /// 租户名称，1-255个字符
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    /// 租户代码，同时作为子域名，1-63个字符，只能包含字母、数字和连字符，不区分大小写
+    #[validate(length(min = 1, max = 63))]
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CreateTenantResponse {
+    /// 租户ID
+    pub tenant_id: Uuid,
+    /// 响应消息
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct RenameTenantRequest {
+    /// 租户名称，1-255个字符
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct TenantStatusChangeRequest {
+    /// 暂停或重新启用的原因
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct TenantResponse {
+    /// 租户ID
+    pub id: Uuid,
+    /// 租户名称
+    pub name: String,
+    /// 租户代码
+    pub code: String,
+    /// 状态：active 或 suspended
+    pub status: String,
+    /// 创建时间
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// 更新时间
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<tenant_view::Model> for TenantResponse {
+    fn from(tenant: tenant_view::Model) -> Self {
+        Self {
+            id: tenant.id,
+            name: tenant.name,
+            code: tenant.code,
+            status: tenant.status,
+            created_at: tenant.created_at,
+            updated_at: tenant.updated_at,
+        }
+    }
+}
+
+/// 获取租户列表
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/tenants",
+    tag = "admin",
+    params(ListTenantsQuery),
+    responses(
+        (status = 200, description = "获取租户列表成功", body = Vec<TenantResponse>),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn list_tenants(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+    Query(query): Query<ListTenantsQuery>,
+) -> Result<Json<Vec<TenantResponse>>, AppError> {
+    let limit = Some(query.limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE));
+
+    let tenants = state.query_service
+        .get_tenants(query.status.as_deref(), limit, query.offset)
+        .await?;
+
+    Ok(Json(tenants.into_iter().map(TenantResponse::from).collect()))
+}
+
+/// 根据ID获取租户信息
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/tenants/{tenant_id}",
+    tag = "admin",
+    params(
+        ("tenant_id" = Uuid, Path, description = "租户ID")
+    ),
+    responses(
+        (status = 200, description = "获取租户信息成功", body = TenantResponse),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限"),
+        (status = 404, description = "租户不存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn get_tenant(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+    Path(tenant_id): Path<Uuid>,
+) -> Result<Json<TenantResponse>, AppError> {
+    let tenant = state.query_service
+        .get_tenant_by_id(tenant_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Tenant with ID {} not found", tenant_id)))?;
+
+    Ok(Json(tenant.into()))
+}
+
+/// 创建租户
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/tenants",
+    tag = "admin",
+    request_body = CreateTenantRequest,
+    responses(
+        (status = 201, description = "租户创建成功", body = CreateTenantResponse),
+        (status = 400, description = "请求参数错误"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限"),
+        (status = 409, description = "租户代码已存在"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn create_tenant(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+    metadata: EventMetadata,
+    Json(payload): Json<CreateTenantRequest>,
+) -> Result<(StatusCode, Json<CreateTenantResponse>), AppError> {
+    payload.validate()?;
+
+    // 租户代码用作子域名，统一保存为小写
+    let code = payload.code.to_ascii_lowercase();
+
+    // 读模型最终一致，这里只能拦截已投影的重复代码
+    if state.tenants.find_tenant_by_code(&code).await?.is_some() {
+        return Err(AppError::Conflict(format!("Tenant with code {} already exists", code)));
+    }
+
+    let command = CreateTenantCommand {
+        name: payload.name,
+        code,
+    };
+    let tenant_id = state.tenant_service.create_tenant(command, &metadata).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateTenantResponse {
+            tenant_id,
+            message: "Tenant created successfully".to_string(),
+        }),
+    ))
+}
+
+/// 修改租户名称，租户代码不可修改
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/tenants/{tenant_id}",
+    tag = "admin",
+    params(
+        ("tenant_id" = Uuid, Path, description = "租户ID")
+    ),
+    request_body = RenameTenantRequest,
+    responses(
+        (status = 204, description = "租户更新成功"),
+        (status = 400, description = "请求参数错误或名称未变化"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限"),
+        (status = 404, description = "租户不存在"),
+        (status = 409, description = "租户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn rename_tenant(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+    Path(tenant_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<RenameTenantRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+
+    let command = RenameTenantCommand {
+        tenant_id,
+        name: payload.name,
+    };
+    state.tenant_service.rename_tenant(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 暂停租户，租户下的用户无法登录，已签发的访问令牌和刷新令牌随之失效
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/tenants/{tenant_id}/suspend",
+    tag = "admin",
+    params(
+        ("tenant_id" = Uuid, Path, description = "租户ID")
+    ),
+    request_body = TenantStatusChangeRequest,
+    responses(
+        (status = 204, description = "租户暂停成功"),
+        (status = 400, description = "请求参数错误、租户已暂停或为平台租户"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限"),
+        (status = 404, description = "租户不存在"),
+        (status = 409, description = "租户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn suspend_tenant(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+    Path(tenant_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<TenantStatusChangeRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+
+    // 暂停平台租户会让所有平台管理员失去访问权限
+    if state.config.platform.tenant_id == Some(tenant_id) {
+        return Err(AppError::DomainError("The platform tenant cannot be suspended".to_string()));
+    }
+
+    let command = SuspendTenantCommand {
+        tenant_id,
+        reason: payload.reason,
+    };
+    state.tenant_service.suspend_tenant(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 重新启用已暂停的租户
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/tenants/{tenant_id}/reactivate",
+    tag = "admin",
+    params(
+        ("tenant_id" = Uuid, Path, description = "租户ID")
+    ),
+    request_body = TenantStatusChangeRequest,
+    responses(
+        (status = 204, description = "租户重新启用成功"),
+        (status = 400, description = "请求参数错误或租户未暂停"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无平台管理权限"),
+        (status = 404, description = "租户不存在"),
+        (status = 409, description = "租户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn reactivate_tenant(
+    State(state): State<AppState>,
+    _admin: PlatformAdmin,
+    Path(tenant_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<TenantStatusChangeRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+
+    let command = ReactivateTenantCommand {
+        tenant_id,
+        reason: payload.reason,
+    };
+    state.tenant_service.reactivate_tenant(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
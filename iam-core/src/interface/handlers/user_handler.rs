@@ -7,9 +7,19 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::domain::identity_access::commands::RegisterUserCommand;
+use crate::application::dtos as user_view;
+use crate::domain::identity_access::commands::{
+    DeactivateUserCommand, LockUserCommand, ReactivateUserCommand, RegisterUserCommand, UnlockUserCommand,
+    UpdateUserCommand,
+};
 use crate::error::AppError;
-use crate::interface::middleware::AppState;
+use crate::infrastructure::persistence::event_store::EventMetadata;
+use crate::interface::middleware::{
+    auth::{is_platform_admin, AuthenticatedUser},
+    authorization::{OwnerOrPermission, RequirePermission, UserLock, UserRead, UserWrite},
+    tenant::TenantContext,
+    AppState,
+};
 
 #[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
 pub struct RegisterUserRequest {
@@ -24,6 +34,32 @@ pub struct RegisterUserRequest {
     pub password: String,
 }
 
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct UpdateUserRequest {
+    /// 新用户名，3-50个字符
+    #[validate(length(min = 3, max = 50))]
+    pub username: Option<String>,
+    /// 新邮箱地址
+    #[validate(email)]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct UserStatusChangeRequest {
+    /// 停用或重新启用的原因
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct LockUserRequest {
+    /// 锁定原因
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+    /// 锁定到期时间，为空时需要手动解锁
+    pub locked_until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct RegisterUserResponse {
     /// 用户ID
@@ -40,11 +76,28 @@ pub struct UserResponse {
     pub username: String,
     /// 邮箱地址
     pub email: String,
+    /// 用户状态：active、inactive 或 locked
+    pub status: String,
+    /// 锁定到期时间
+    pub locked_until: Option<chrono::DateTime<chrono::Utc>>,
     /// 创建时间
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
-/// 注册新用户
+impl From<user_view::Model> for UserResponse {
+    fn from(user: user_view::Model) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            status: user.status,
+            locked_until: user.locked_until,
+            created_at: user.created_at,
+        }
+    }
+}
+
+/// 注册新用户；平台租户的用户只能由平台管理员创建
 #[utoipa::path(
     post,
     path = "/api/v1/users",
@@ -53,31 +106,48 @@ pub struct UserResponse {
     responses(
         (status = 201, description = "用户注册成功", body = RegisterUserResponse),
         (status = 400, description = "请求参数错误"),
+        (status = 401, description = "未指明租户"),
+        (status = 403, description = "租户未启用，或非平台管理员注册到平台租户"),
+        (status = 404, description = "租户不存在"),
         (status = 409, description = "用户名或邮箱已存在"),
         (status = 500, description = "服务器内部错误")
     )
 )]
 pub async fn register_user(
     State(state): State<AppState>,
+    user: Option<AuthenticatedUser>,
+    tenant: TenantContext,
+    metadata: EventMetadata,
     Json(payload): Json<RegisterUserRequest>,
 ) -> Result<(StatusCode, Json<RegisterUserResponse>), AppError> {
     // 验证输入
     payload.validate()?;
 
+    // 平台租户的用户可以被授予平台管理员权限，不开放自助注册
+    if state.config.platform.tenant_id == Some(tenant.tenant_id) {
+        let is_admin = match &user {
+            Some(user) => is_platform_admin(&state, user).await?,
+            None => false,
+        };
+        if !is_admin {
+            return Err(AppError::AuthorizationError("Only platform administrators can add users to the platform tenant".to_string()));
+        }
+    }
+
     // 密码哈希
     let password_hash = bcrypt::hash(&payload.password, bcrypt::DEFAULT_COST)
         .map_err(|e| AppError::DomainError(format!("Password hashing failed: {}", e)))?;
 
     // 创建命令
     let command = RegisterUserCommand {
-        tenant_id: Uuid::new_v4(), // TODO: 从认证上下文获取租户ID
+        tenant_id: tenant.tenant_id,
         username: payload.username,
         email: payload.email,
         password_hash,
     };
 
     // 执行命令
-    let user_id = state.user_service.register_user(command).await?;
+    let user_id = state.user_service.register_user(command, &metadata).await?;
 
     Ok((
         StatusCode::CREATED,
@@ -88,7 +158,7 @@ pub async fn register_user(
     ))
 }
 
-/// 根据ID获取用户信息
+/// 根据ID获取用户信息，用户本人或拥有 user:read 权限的同租户用户可以查看
 #[utoipa::path(
     get,
     path = "/api/v1/users/{user_id}",
@@ -106,6 +176,7 @@ pub async fn register_user(
 )]
 pub async fn get_user(
     State(state): State<AppState>,
+    guard: OwnerOrPermission<UserRead>,
     Path(user_id): Path<Uuid>,
 ) -> Result<Json<UserResponse>, AppError> {
     let user = state.query_service
@@ -113,44 +184,288 @@ pub async fn get_user(
         .await?
         .ok_or_else(|| AppError::NotFound(format!("User with ID {} not found", user_id)))?;
 
-    Ok(Json(UserResponse {
-        id: user.id,
-        username: user.username,
-        email: user.email,
-        created_at: user.created_at,
-    }))
+    // 权限只在本租户内有效
+    if !guard.is_owner && user.tenant_id != guard.user.tenant_id {
+        return Err(AppError::AuthorizationError("User belongs to a different tenant".to_string()));
+    }
+
+    Ok(Json(user.into()))
 }
 
-/// 获取用户列表
+/// 获取用户列表，需要 user:read 权限
 #[utoipa::path(
     get,
     path = "/api/v1/users",
     tag = "users",
     responses(
         (status = 200, description = "获取用户列表成功", body = Vec<UserResponse>),
-        (status = 401, description = "未认证"),
-        (status = 403, description = "无权限"),
+        (status = 401, description = "未认证或未指明租户"),
+        (status = 403, description = "无权限或租户未启用"),
+        (status = 404, description = "租户不存在"),
         (status = 500, description = "服务器内部错误")
     )
 )]
 pub async fn list_users(
     State(state): State<AppState>,
+    _guard: RequirePermission<UserRead>,
+    tenant: TenantContext,
 ) -> Result<Json<Vec<UserResponse>>, AppError> {
-    // TODO: 从请求中获取租户ID和分页参数
-    let tenant_id = uuid::Uuid::new_v4(); // 临时使用随机UUID
+    // TODO: 从请求中获取分页参数
     let users = state.query_service
-        .get_users_by_tenant(tenant_id, Some(100), Some(0))
+        .get_users_by_tenant(tenant.tenant_id, Some(100), Some(0))
         .await?;
 
-    let user_responses: Vec<UserResponse> = users
-        .into_iter()
-        .map(|user| UserResponse {
-            id: user.id,
-            username: user.username,
-            email: user.email,
-            created_at: user.created_at,
-        })
-        .collect();
+    Ok(Json(users.into_iter().map(UserResponse::from).collect()))
+}
+
+/// 更新用户信息，PUT 与 PATCH 均只修改请求中提供的字段；用户可以修改自己的信息，修改他人需要 user:write 权限
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/{user_id}",
+    tag = "users",
+    params(
+        ("user_id" = Uuid, Path, description = "用户ID")
+    ),
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 204, description = "用户更新成功"),
+        (status = 400, description = "请求参数错误或用户已停用"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或用户属于其他租户"),
+        (status = 404, description = "用户不存在"),
+        (status = 409, description = "用户名或邮箱已存在，或用户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn update_user(
+    State(state): State<AppState>,
+    guard: OwnerOrPermission<UserWrite>,
+    Path(user_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<UpdateUserRequest>,
+) -> Result<StatusCode, AppError> {
+    // 验证输入
+    payload.validate()?;
+
+    // 权限只在本租户内有效
+    let tenant_id = state.user_service.get_user(user_id).await?.tenant_id();
+    if !guard.is_owner && tenant_id != guard.user.tenant_id {
+        return Err(AppError::AuthorizationError("User belongs to a different tenant".to_string()));
+    }
+
+    // 读模型最终一致，这里只能拦截已投影的重复用户名或邮箱
+    if let Some(ref username) = payload.username {
+        let existing = state.query_service.get_user_by_username(username, tenant_id).await?;
+        if existing.is_some_and(|user| user.id != user_id) {
+            return Err(AppError::Conflict(format!("Username {} already exists", username)));
+        }
+    }
+    if let Some(ref email) = payload.email {
+        let existing = state.query_service.get_user_by_email(email, tenant_id).await?;
+        if existing.is_some_and(|user| user.id != user_id) {
+            return Err(AppError::Conflict(format!("Email {} already exists", email)));
+        }
+    }
+
+    let command = UpdateUserCommand {
+        user_id,
+        username: payload.username,
+        email: payload.email,
+    };
+    state.user_service.update_user(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 部分更新用户信息，权限要求与 PUT 相同
+#[utoipa::path(
+    patch,
+    path = "/api/v1/users/{user_id}",
+    tag = "users",
+    params(
+        ("user_id" = Uuid, Path, description = "用户ID")
+    ),
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 204, description = "用户更新成功"),
+        (status = 400, description = "请求参数错误或用户已停用"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或用户属于其他租户"),
+        (status = 404, description = "用户不存在"),
+        (status = 409, description = "用户名或邮箱已存在，或用户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn patch_user(
+    state: State<AppState>,
+    guard: OwnerOrPermission<UserWrite>,
+    path: Path<Uuid>,
+    metadata: EventMetadata,
+    payload: Json<UpdateUserRequest>,
+) -> Result<StatusCode, AppError> {
+    update_user(state, guard, path, metadata, payload).await
+}
+
+/// 停用用户，需要 user:write 权限，只能操作本租户的用户
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{user_id}/deactivate",
+    tag = "users",
+    params(
+        ("user_id" = Uuid, Path, description = "用户ID")
+    ),
+    request_body = UserStatusChangeRequest,
+    responses(
+        (status = 204, description = "用户停用成功"),
+        (status = 400, description = "请求参数错误或用户已停用"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或用户属于其他租户"),
+        (status = 404, description = "用户不存在"),
+        (status = 409, description = "用户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn deactivate_user(
+    State(state): State<AppState>,
+    guard: RequirePermission<UserWrite>,
+    Path(user_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<UserStatusChangeRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+    ensure_same_tenant(&state, guard.user.tenant_id, user_id).await?;
+
+    let command = DeactivateUserCommand {
+        user_id,
+        reason: payload.reason,
+    };
+    state.user_service.deactivate_user(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 重新启用已停用的用户，需要 user:write 权限，只能操作本租户的用户
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{user_id}/reactivate",
+    tag = "users",
+    params(
+        ("user_id" = Uuid, Path, description = "用户ID")
+    ),
+    request_body = UserStatusChangeRequest,
+    responses(
+        (status = 204, description = "用户重新启用成功"),
+        (status = 400, description = "请求参数错误或用户未停用"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或用户属于其他租户"),
+        (status = 404, description = "用户不存在"),
+        (status = 409, description = "用户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn reactivate_user(
+    State(state): State<AppState>,
+    guard: RequirePermission<UserWrite>,
+    Path(user_id): Path<Uuid>,
+    metadata: EventMetadata,
+    Json(payload): Json<UserStatusChangeRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+    ensure_same_tenant(&state, guard.user.tenant_id, user_id).await?;
+
+    let command = ReactivateUserCommand {
+        user_id,
+        reason: payload.reason,
+    };
+    state.user_service.reactivate_user(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 锁定用户账户，需要 user:lock 权限，只能操作本租户的用户
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{user_id}/lock",
+    tag = "users",
+    params(
+        ("user_id" = Uuid, Path, description = "用户ID")
+    ),
+    request_body = LockUserRequest,
+    responses(
+        (status = 204, description = "用户锁定成功"),
+        (status = 400, description = "请求参数错误、用户已停用或已锁定"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或用户属于其他租户"),
+        (status = 404, description = "用户不存在"),
+        (status = 409, description = "用户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn lock_user(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    guard: RequirePermission<UserLock>,
+    metadata: EventMetadata,
+    Json(payload): Json<LockUserRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+    ensure_same_tenant(&state, guard.user.tenant_id, user_id).await?;
+
+    let command = LockUserCommand {
+        user_id,
+        reason: payload.reason,
+        locked_until: payload.locked_until,
+        locked_by: Some(guard.user.user_id),
+    };
+    state.user_service.lock_user(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 解锁用户账户，需要 user:lock 权限，只能操作本租户的用户
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{user_id}/unlock",
+    tag = "users",
+    params(
+        ("user_id" = Uuid, Path, description = "用户ID")
+    ),
+    request_body = UserStatusChangeRequest,
+    responses(
+        (status = 204, description = "用户解锁成功"),
+        (status = 400, description = "请求参数错误或用户未锁定"),
+        (status = 401, description = "未认证"),
+        (status = 403, description = "无权限或用户属于其他租户"),
+        (status = 404, description = "用户不存在"),
+        (status = 409, description = "用户已被其他请求修改"),
+        (status = 500, description = "服务器内部错误")
+    )
+)]
+pub async fn unlock_user(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    guard: RequirePermission<UserLock>,
+    metadata: EventMetadata,
+    Json(payload): Json<UserStatusChangeRequest>,
+) -> Result<StatusCode, AppError> {
+    payload.validate()?;
+    ensure_same_tenant(&state, guard.user.tenant_id, user_id).await?;
+
+    let command = UnlockUserCommand {
+        user_id,
+        reason: payload.reason,
+        unlocked_by: Some(guard.user.user_id),
+    };
+    state.user_service.unlock_user(command, &metadata).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    Ok(Json(user_responses))
+/// 确认目标用户属于操作所在的租户
+pub(crate) async fn ensure_same_tenant(state: &AppState, tenant_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+    let user = state.user_service.get_user(user_id).await?;
+    if user.tenant_id() != tenant_id {
+        return Err(AppError::AuthorizationError("User belongs to a different tenant".to_string()));
+    }
+    Ok(())
 }
@@ -1,16 +1,19 @@
 use axum::{
-    extract::{Request, State},
-    http::header::AUTHORIZATION,
+    async_trait,
+    extract::{FromRequestParts, Request, State},
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap},
     middleware::Next,
     response::Response,
 };
-use jsonwebtoken::{decode, DecodingKey, Validation, Algorithm};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::config::AppConfig;
+use crate::application::services::PLATFORM_ADMIN_PERMISSION;
+use crate::domain::identity_access::aggregates::UserStatus;
 use crate::error::AppError;
+use crate::infrastructure::security::KeyRing;
+use crate::interface::middleware::AppState;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
@@ -19,6 +22,9 @@ pub struct Claims {
     pub tenant_id: String,
     pub exp: u64,
     pub iat: u64,
+    /// 令牌ID，登出时按它吊销单个令牌；旧令牌没有该字段
+    #[serde(default)]
+    pub jti: String,
 }
 
 #[derive(Debug, Clone)]
@@ -26,17 +32,32 @@ pub struct AuthenticatedUser {
     pub user_id: Uuid,
     pub username: String,
     pub tenant_id: Uuid,
+    /// 访问令牌的ID（jti）
+    pub token_id: Option<String>,
+    /// 访问令牌的签发时间
+    pub issued_at: DateTime<Utc>,
+    /// 访问令牌的过期时间
+    pub expires_at: DateTime<Utc>,
 }
 
 /// JWT认证中间件
 pub async fn auth_middleware(
-    State(config): State<Arc<AppConfig>>,
+    State(state): State<AppState>,
     mut request: Request,
     next: Next,
 ) -> Result<Response, AppError> {
+    let authenticated_user = authenticate(request.headers(), &state).await?;
+
+    // 将用户信息添加到请求扩展中
+    request.extensions_mut().insert(authenticated_user);
+
+    Ok(next.run(request).await)
+}
+
+/// 从 Authorization 请求头中验证 Bearer token 并解析出认证用户，已吊销的令牌会被拒绝
+pub async fn authenticate(headers: &HeaderMap, state: &AppState) -> Result<AuthenticatedUser, AppError> {
     // 从请求头中获取Authorization token
-    let auth_header = request
-        .headers()
+    let auth_header = headers
         .get(AUTHORIZATION)
         .and_then(|header| header.to_str().ok())
         .ok_or_else(|| AppError::AuthenticationError("Missing authorization header".to_string()))?;
@@ -49,45 +70,127 @@ pub async fn auth_middleware(
     let token = &auth_header[7..]; // 移除"Bearer "前缀
 
     // 验证JWT token
-    let claims = validate_token(token, &config.jwt.secret)?;
+    let claims = validate_token(token, &state.key_ring)?;
 
-    // 将用户信息添加到请求扩展中
-    let authenticated_user = AuthenticatedUser {
+    let user = AuthenticatedUser {
         user_id: Uuid::parse_str(&claims.sub)
             .map_err(|_| AppError::AuthenticationError("Invalid user ID in token".to_string()))?,
         username: claims.username,
         tenant_id: Uuid::parse_str(&claims.tenant_id)
             .map_err(|_| AppError::AuthenticationError("Invalid tenant ID in token".to_string()))?,
+        token_id: Some(claims.jti).filter(|jti| !jti.is_empty()),
+        issued_at: timestamp(claims.iat)?,
+        expires_at: timestamp(claims.exp)?,
     };
 
-    request.extensions_mut().insert(authenticated_user);
+    // 检查令牌是否已在登出时被吊销
+    if state
+        .token_revocations
+        .is_revoked(user.token_id.as_deref(), user.user_id, user.issued_at)
+        .await?
+    {
+        return Err(AppError::AuthenticationError("Token has been revoked".to_string()));
+    }
 
-    Ok(next.run(request).await)
+    ensure_tenant_not_suspended(state, user.tenant_id).await?;
+    ensure_user_active(state, user.user_id).await?;
+
+    Ok(user)
 }
 
-/// 验证JWT token
-pub fn validate_token(token: &str, secret: &str) -> Result<Claims, AppError> {
-    let decoding_key = DecodingKey::from_secret(secret.as_ref());
-    let validation = Validation::new(Algorithm::HS256);
+/// 拒绝已停用或锁定用户的令牌；以事件存储中的用户状态为准，不依赖有延迟的读模型，
+/// 与策略决策接口的检查一致。事件存储中不存在的用户不在此处拦截
+pub async fn ensure_user_active(state: &AppState, user_id: Uuid) -> Result<(), AppError> {
+    let user = match state.user_service.get_user(user_id).await {
+        Ok(user) => user,
+        Err(AppError::AggregateNotFound(_)) => return Ok(()),
+        Err(e) => return Err(e),
+    };
 
-    let token_data = decode::<Claims>(token, &decoding_key, &validation)
-        .map_err(|e| AppError::AuthenticationError(format!("Invalid token: {}", e)))?;
+    if *user.status() == UserStatus::Inactive || user.is_locked_at(Utc::now()) {
+        return Err(AppError::AuthenticationError("User is deactivated or locked".to_string()));
+    }
+    Ok(())
+}
+
+/// 拒绝已暂停租户的令牌；租户表中不存在的租户不在此处拦截
+pub async fn ensure_tenant_not_suspended(state: &AppState, tenant_id: Uuid) -> Result<(), AppError> {
+    match state.tenants.find_tenant(tenant_id).await? {
+        Some(tenant) if !tenant.is_active() => {
+            Err(AppError::AuthenticationError(format!("Tenant {} is not active", tenant.code)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 把令牌中的秒级时间戳转换为时间
+fn timestamp(seconds: u64) -> Result<DateTime<Utc>, AppError> {
+    i64::try_from(seconds)
+        .ok()
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .ok_or_else(|| AppError::AuthenticationError("Invalid timestamp in token".to_string()))
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AuthenticatedUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        // 优先使用认证中间件已解析的用户
+        match parts.extensions.get::<AuthenticatedUser>() {
+            Some(user) => Ok(user.clone()),
+            None => authenticate(&parts.headers, state).await,
+        }
+    }
+}
+
+/// 平台管理员提取器，只允许拥有 platform:admin 权限的平台租户用户通过
+#[derive(Debug, Clone)]
+pub struct PlatformAdmin(pub AuthenticatedUser);
+
+#[async_trait]
+impl FromRequestParts<AppState> for PlatformAdmin {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let user = AuthenticatedUser::from_request_parts(parts, state).await?;
+
+        if is_platform_admin(state, &user).await? {
+            Ok(PlatformAdmin(user))
+        } else {
+            Err(AppError::AuthorizationError("Platform administrator required".to_string()))
+        }
+    }
+}
+
+/// 用户是否平台管理员：属于平台租户并拥有 platform:admin 权限，仅属于平台租户不够
+pub async fn is_platform_admin(state: &AppState, user: &AuthenticatedUser) -> Result<bool, AppError> {
+    if state.config.platform.tenant_id != Some(user.tenant_id) {
+        return Ok(false);
+    }
+
+    state.authorization_service.has_permission(user.user_id, PLATFORM_ADMIN_PERMISSION).await
+}
+
+/// 验证JWT token，按令牌头中的 kid 选择验证密钥
+pub fn validate_token(token: &str, key_ring: &KeyRing) -> Result<Claims, AppError> {
+    let claims: Claims = key_ring.verify(token)?;
 
     // 检查token是否过期
     let now = chrono::Utc::now().timestamp() as u64;
-    if token_data.claims.exp < now {
+    if claims.exp < now {
         return Err(AppError::AuthenticationError("Token has expired".to_string()));
     }
 
-    Ok(token_data.claims)
+    Ok(claims)
 }
 
-/// 生成JWT token
+/// 生成JWT token，使用密钥环当前的签名密钥
 pub fn generate_token(
     user_id: Uuid,
     username: String,
     tenant_id: Uuid,
-    secret: &str,
+    key_ring: &KeyRing,
     expiration_hours: u64,
 ) -> Result<String, AppError> {
     let now = chrono::Utc::now().timestamp() as u64;
@@ -99,13 +202,10 @@ pub fn generate_token(
         tenant_id: tenant_id.to_string(),
         exp,
         iat: now,
+        jti: Uuid::new_v4().to_string(),
     };
 
-    let encoding_key = jsonwebtoken::EncodingKey::from_secret(secret.as_ref());
-    let header = jsonwebtoken::Header::new(Algorithm::HS256);
-
-    jsonwebtoken::encode(&header, &claims, &encoding_key)
-        .map_err(|e| AppError::InternalError(format!("Failed to generate token: {}", e)))
+    key_ring.sign(&claims)
 }
 
 /// 从请求中提取认证用户信息
@@ -0,0 +1,270 @@
+use std::marker::PhantomData;
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, OriginalUri, RawPathParams, Request, State},
+    http::request::Parts,
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+use crate::application::services::{RouteRequirement, RouteTemplate};
+use crate::config::UnmatchedRoutePolicy;
+use crate::error::AppError;
+use crate::interface::middleware::auth::{authenticate, is_platform_admin, AuthenticatedUser};
+use crate::interface::middleware::AppState;
+
+/// 权限守卫可以要求的权限代码，每个代码对应一个标记类型
+pub trait PermissionCode: Send + Sync + 'static {
+    const CODE: &'static str;
+}
+
+/// user:read，查看本租户的用户
+#[derive(Debug, Clone, Copy)]
+pub struct UserRead;
+
+impl PermissionCode for UserRead {
+    const CODE: &'static str = "user:read";
+}
+
+/// user:write，修改、停用和重新启用本租户的用户
+#[derive(Debug, Clone, Copy)]
+pub struct UserWrite;
+
+impl PermissionCode for UserWrite {
+    const CODE: &'static str = "user:write";
+}
+
+/// user:lock，锁定和解锁本租户的用户
+#[derive(Debug, Clone, Copy)]
+pub struct UserLock;
+
+impl PermissionCode for UserLock {
+    const CODE: &'static str = "user:lock";
+}
+
+/// role:read，查看本租户的角色
+#[derive(Debug, Clone, Copy)]
+pub struct RoleRead;
+
+impl PermissionCode for RoleRead {
+    const CODE: &'static str = "role:read";
+}
+
+/// role:write，创建、修改和删除本租户的角色
+#[derive(Debug, Clone, Copy)]
+pub struct RoleWrite;
+
+impl PermissionCode for RoleWrite {
+    const CODE: &'static str = "role:write";
+}
+
+/// role:assign，为本租户的用户分配和移除角色
+#[derive(Debug, Clone, Copy)]
+pub struct RoleAssign;
+
+impl PermissionCode for RoleAssign {
+    const CODE: &'static str = "role:assign";
+}
+
+/// role:grant，为本租户的角色授予和撤销权限
+#[derive(Debug, Clone, Copy)]
+pub struct RoleGrant;
+
+impl PermissionCode for RoleGrant {
+    const CODE: &'static str = "role:grant";
+}
+
+/// organization:read，查看本租户的组织树和组织成员
+#[derive(Debug, Clone, Copy)]
+pub struct OrganizationRead;
+
+impl PermissionCode for OrganizationRead {
+    const CODE: &'static str = "organization:read";
+}
+
+/// organization:write，创建、修改、移动和删除本租户的组织
+#[derive(Debug, Clone, Copy)]
+pub struct OrganizationWrite;
+
+impl PermissionCode for OrganizationWrite {
+    const CODE: &'static str = "organization:write";
+}
+
+/// organization:assign，把本租户的用户加入和移出组织
+#[derive(Debug, Clone, Copy)]
+pub struct OrganizationAssign;
+
+impl PermissionCode for OrganizationAssign {
+    const CODE: &'static str = "organization:assign";
+}
+
+/// permission:read，查看权限目录，为角色授权时据此选择权限
+#[derive(Debug, Clone, Copy)]
+pub struct PermissionRead;
+
+impl PermissionCode for PermissionRead {
+    const CODE: &'static str = "permission:read";
+}
+
+/// authz:check，代其他服务检查用户的权限
+#[derive(Debug, Clone, Copy)]
+pub struct AuthzCheck;
+
+impl PermissionCode for AuthzCheck {
+    const CODE: &'static str = "authz:check";
+}
+
+/// 权限守卫提取器，只允许拥有权限 `P` 的已认证用户通过
+///
+/// 例如处理函数参数 `_guard: RequirePermission<UserRead>` 要求 user:read 权限；
+/// 权限按认证用户的有效权限判断，包括通过上级菜单继承的权限
+#[derive(Debug, Clone)]
+pub struct RequirePermission<P: PermissionCode> {
+    pub user: AuthenticatedUser,
+    permission: PhantomData<P>,
+}
+
+#[async_trait]
+impl<P: PermissionCode> FromRequestParts<AppState> for RequirePermission<P> {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let user = AuthenticatedUser::from_request_parts(parts, state).await?;
+        ensure_permission(state, &user, P::CODE).await?;
+
+        Ok(Self { user, permission: PhantomData })
+    }
+}
+
+/// 用于自助接口的守卫提取器：路径参数 `id` 指向认证用户本人时直接通过，否则要求权限 `P`
+///
+/// 通过权限访问他人时 `is_owner` 为 false，处理函数仍需确认目标属于认证用户的租户
+#[derive(Debug, Clone)]
+pub struct OwnerOrPermission<P: PermissionCode> {
+    pub user: AuthenticatedUser,
+    pub is_owner: bool,
+    permission: PhantomData<P>,
+}
+
+#[async_trait]
+impl<P: PermissionCode> FromRequestParts<AppState> for OwnerOrPermission<P> {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let user = AuthenticatedUser::from_request_parts(parts, state).await?;
+
+        let params = RawPathParams::from_request_parts(parts, state).await
+            .map_err(|_| AppError::InternalError("Owner guard used on a route without path parameters".to_string()))?;
+        let owner_id = params
+            .iter()
+            .find(|(name, _)| *name == "id")
+            .and_then(|(_, value)| Uuid::parse_str(value).ok());
+
+        let is_owner = owner_id == Some(user.user_id);
+        if !is_owner {
+            ensure_permission(state, &user, P::CODE).await?;
+        }
+
+        Ok(Self { user, is_owner, permission: PhantomData })
+    }
+}
+
+/// 拒绝没有指定权限的用户；平台管理员拥有全部权限，用于初始化各租户的角色和授权
+pub async fn ensure_permission(state: &AppState, user: &AuthenticatedUser, code: &str) -> Result<(), AppError> {
+    if state.authorization_service.has_permission(user.user_id, code).await?
+        || is_platform_admin(state, user).await?
+    {
+        Ok(())
+    } else {
+        Err(AppError::AuthorizationError(format!("Permission {} required", code)))
+    }
+}
+
+/// 处理函数使用 `OwnerOrPermission` 守卫的路由，路径参数 `id` 指向认证用户本人时路由授权同样放行
+const OWNER_ROUTES: [&str; 3] = [
+    "GET /api/v1/users/:id",
+    "PUT /api/v1/users/:id",
+    "PATCH /api/v1/users/:id",
+];
+
+/// 基于 API 权限路由的授权中间件
+///
+/// 按请求方法和路径匹配权限目录中 API 权限的路由，匹配时要求认证用户拥有其中任一权限；
+/// 没有匹配的路由按配置放行或拒绝，豁免的路由不做检查。新增接口只需登记 API 权限即可受保护。
+/// 与权限守卫的规则一致，平台管理员和自助路由上的用户本人总是放行，
+/// 因此权限目录为空时平台管理员仍可登记第一批 API 权限
+pub async fn route_authorization_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    // 嵌套路由看到的路径已去掉前缀，按完整路径匹配
+    let path = match request.extensions().get::<OriginalUri>() {
+        Some(uri) => uri.path().to_string(),
+        None => request.uri().path().to_string(),
+    };
+    let method = request.method().as_str().to_string();
+
+    let codes = match state.route_permissions.resolve(&method, &path).await? {
+        RouteRequirement::Exempt => return Ok(next.run(request).await),
+        RouteRequirement::Unmatched
+            if state.config.authorization.unmatched_route_policy == UnmatchedRoutePolicy::Allow =>
+        {
+            return Ok(next.run(request).await);
+        }
+        RouteRequirement::Unmatched => Vec::new(),
+        RouteRequirement::Permissions(codes) => codes,
+    };
+
+    let user = match authenticate(request.headers(), &state).await {
+        Ok(user) => user,
+        // 拒绝未匹配的路由时不区分调用方是否已认证
+        Err(_) if codes.is_empty() => return Err(route_denied(&codes)),
+        Err(e) => return Err(e),
+    };
+    if !route_allows(&state, &user, &method, &path, &codes).await? {
+        return Err(route_denied(&codes));
+    }
+
+    // 处理函数中的认证提取器复用已解析的用户
+    request.extensions_mut().insert(user);
+
+    Ok(next.run(request).await)
+}
+
+/// 用户是否拥有路由要求的任一权限、是该自助路由指向的本人，或是平台管理员
+async fn route_allows(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    method: &str,
+    path: &str,
+    codes: &[String],
+) -> Result<bool, AppError> {
+    let is_owner = OWNER_ROUTES
+        .iter()
+        .filter_map(|route| RouteTemplate::parse(route))
+        .find(|route| route.matches(method, path))
+        .and_then(|route| route.param(path, "id").and_then(|id| Uuid::parse_str(id).ok()))
+        == Some(user.user_id);
+    if is_owner {
+        return Ok(true);
+    }
+
+    for code in codes {
+        if state.authorization_service.has_permission(user.user_id, code).await? {
+            return Ok(true);
+        }
+    }
+
+    is_platform_admin(state, user).await
+}
+
+fn route_denied(codes: &[String]) -> AppError {
+    if codes.is_empty() {
+        AppError::AuthorizationError("No API permission covers this route".to_string())
+    } else {
+        AppError::AuthorizationError(format!("Permission {} required", codes.join(" or ")))
+    }
+}
@@ -1,32 +1,82 @@
 use std::sync::Arc;
 
-use crate::application::services::{UserService, QueryService};
+use crate::application::services::{
+    AuthorizationService, LoginGuard, OrganizationService, PermissionService, PolicyDecisionService, QueryService,
+    RefreshTokenService, RoleService, RoutePermissionService, TenantService, UserService,
+};
 use crate::config::AppConfig;
 use crate::infrastructure::persistence::event_store::EventStore;
+use crate::infrastructure::persistence::projection::ProjectionRebuilder;
+use crate::infrastructure::persistence::tenants::TenantStore;
+use crate::infrastructure::persistence::token_revocations::TokenRevocationStore;
+use crate::infrastructure::security::KeyRing;
 
 pub mod auth;
+pub mod authorization;
+pub mod request_metadata;
+pub mod tenant;
 pub mod validation;
 
 /// 应用程序状态，包含所有服务依赖
 #[derive(Clone)]
 pub struct AppState {
     pub user_service: Arc<UserService>,
+    pub role_service: Arc<RoleService>,
+    pub tenant_service: Arc<TenantService>,
+    pub organization_service: Arc<OrganizationService>,
+    pub permission_service: Arc<PermissionService>,
+    pub authorization_service: Arc<AuthorizationService>,
+    pub route_permissions: Arc<RoutePermissionService>,
+    pub policy_decisions: Arc<PolicyDecisionService>,
     pub query_service: Arc<QueryService>,
+    pub login_guard: Arc<LoginGuard>,
+    pub refresh_tokens: Arc<RefreshTokenService>,
+    pub token_revocations: Arc<dyn TokenRevocationStore>,
+    pub key_ring: Arc<KeyRing>,
+    pub tenants: Arc<dyn TenantStore>,
     pub event_store: Arc<dyn EventStore>,
+    pub projection_rebuilder: Arc<ProjectionRebuilder>,
     pub config: Arc<AppConfig>,
 }
 
 impl AppState {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_service: Arc<UserService>,
+        role_service: Arc<RoleService>,
+        tenant_service: Arc<TenantService>,
+        organization_service: Arc<OrganizationService>,
+        permission_service: Arc<PermissionService>,
+        authorization_service: Arc<AuthorizationService>,
+        route_permissions: Arc<RoutePermissionService>,
+        policy_decisions: Arc<PolicyDecisionService>,
         query_service: Arc<QueryService>,
+        login_guard: Arc<LoginGuard>,
+        refresh_tokens: Arc<RefreshTokenService>,
+        token_revocations: Arc<dyn TokenRevocationStore>,
+        key_ring: Arc<KeyRing>,
+        tenants: Arc<dyn TenantStore>,
         event_store: Arc<dyn EventStore>,
+        projection_rebuilder: Arc<ProjectionRebuilder>,
         config: Arc<AppConfig>,
     ) -> Self {
         Self {
             user_service,
+            role_service,
+            tenant_service,
+            organization_service,
+            permission_service,
+            authorization_service,
+            route_permissions,
+            policy_decisions,
             query_service,
+            login_guard,
+            refresh_tokens,
+            token_revocations,
+            key_ring,
+            tenants,
             event_store,
+            projection_rebuilder,
             config,
         }
     }
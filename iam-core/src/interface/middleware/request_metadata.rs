@@ -0,0 +1,86 @@
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{request::Parts, HeaderMap},
+};
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+use crate::infrastructure::persistence::event_store::EventMetadata;
+use crate::interface::middleware::auth::AuthenticatedUser;
+use crate::interface::middleware::AppState;
+
+/// 关联ID请求头，优先于 X-Request-ID
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// 从请求中提取事件元数据，供命令处理器写入事件存储
+///
+/// - 操作人和租户来自认证中间件写入的 `AuthenticatedUser`（未认证时为空）
+/// - 关联ID取自 X-Correlation-ID 或 X-Request-ID 头部，缺失时生成新的ID
+/// - 客户端IP取自连接地址；只有连接来自配置的可信代理时才采用 X-Forwarded-For 或 X-Real-IP
+#[async_trait]
+impl FromRequestParts<AppState> for EventMetadata {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let authenticated_user = parts.extensions.get::<AuthenticatedUser>();
+        let peer_ip = parts.extensions.get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(addr)| addr.ip());
+
+        Ok(EventMetadata {
+            actor_id: authenticated_user.map(|user| user.user_id),
+            tenant_id: authenticated_user.map(|user| user.tenant_id),
+            correlation_id: Some(
+                correlation_id_from_headers(&parts.headers)
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            ),
+            causation_id: None,
+            client_ip: client_ip(&parts.headers, peer_ip, &state.config.server.trusted_proxies)
+                .map(|ip| ip.to_string()),
+        })
+    }
+}
+
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn correlation_id_from_headers(headers: &HeaderMap) -> Option<String> {
+    header_value(headers, CORRELATION_ID_HEADER)
+        .or_else(|| header_value(headers, REQUEST_ID_HEADER))
+        .map(str::to_string)
+}
+
+/// 客户端可以任意伪造转发头部，只有直接连接方是可信代理时头部才有意义
+fn client_ip(headers: &HeaderMap, peer_ip: Option<IpAddr>, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let peer_ip = peer_ip?;
+    if !trusted_proxies.contains(&peer_ip) {
+        return Some(peer_ip);
+    }
+
+    // 每层代理把连接方追加到 X-Forwarded-For 末尾，从右向左跳过可信代理后的第一个地址是原始客户端；
+    // 更左边的地址可能由客户端伪造
+    if let Some(forwarded) = header_value(headers, "x-forwarded-for") {
+        let mut client_ip = peer_ip;
+        for ip in forwarded.rsplit(',').map(str::trim) {
+            match ip.parse::<IpAddr>() {
+                Ok(ip) => {
+                    client_ip = ip;
+                    if !trusted_proxies.contains(&ip) {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+        return Some(client_ip);
+    }
+
+    header_value(headers, "x-real-ip")
+        .and_then(|ip| ip.parse().ok())
+        .or(Some(peer_ip))
+}
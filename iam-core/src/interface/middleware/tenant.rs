@@ -0,0 +1,150 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::{AUTHORIZATION, HOST}, request::Parts, HeaderMap},
+};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::infrastructure::persistence::tenants::TenantRecord;
+use crate::interface::middleware::auth::{authenticate, is_platform_admin, AuthenticatedUser};
+use crate::interface::middleware::AppState;
+
+/// 指定租户ID的请求头
+pub const TENANT_ID_HEADER: &str = "x-tenant-id";
+
+/// 请求所属的租户，已确认存在且处于启用状态
+///
+/// 依次从认证令牌的 tenant_id、X-Tenant-ID 头部和子域名解析；
+/// 同时提供多个来源时必须指向同一租户，已认证用户不能访问其他租户，
+/// 只有平台管理员可以通过 X-Tenant-ID 头部或子域名代为操作其他租户
+#[derive(Debug, Clone)]
+pub struct TenantContext {
+    pub tenant_id: Uuid,
+    pub code: String,
+    pub name: String,
+}
+
+impl TenantContext {
+    /// 按租户ID加载租户，拒绝不存在或未启用的租户
+    pub async fn load(state: &AppState, tenant_id: Uuid) -> Result<Self, AppError> {
+        let tenant = state
+            .tenants
+            .find_tenant(tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Tenant {} not found", tenant_id)))?;
+
+        Self::active(tenant)
+    }
+
+    /// 按租户编码加载租户，用于登录请求体和子域名中的租户编码
+    pub async fn load_by_code(state: &AppState, code: &str) -> Result<Self, AppError> {
+        let tenant = state
+            .tenants
+            .find_tenant_by_code(code)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Tenant {} not found", code)))?;
+
+        Self::active(tenant)
+    }
+
+    /// 从认证用户和请求头中解析租户，请求未指明租户时返回 None
+    pub async fn resolve(
+        headers: &HeaderMap,
+        user: Option<&AuthenticatedUser>,
+        state: &AppState,
+    ) -> Result<Option<Self>, AppError> {
+        let header_tenant_id = tenant_id_from_headers(headers)?;
+        let host_code = tenant_code_from_host(headers, state.config.tenant.base_domain.as_deref());
+
+        let mut tenant = match user {
+            // 平台管理员指明了租户时以请求指明的租户为准
+            Some(user)
+                if (header_tenant_id.is_some() || host_code.is_some())
+                    && is_platform_admin(state, user).await? =>
+            {
+                None
+            }
+            Some(user) => Some(Self::load(state, user.tenant_id).await?),
+            None => None,
+        };
+
+        if let Some(tenant_id) = header_tenant_id {
+            tenant = match tenant {
+                Some(tenant) => Some(tenant.ensure_same(|tenant| tenant.tenant_id == tenant_id)?),
+                None => Some(Self::load(state, tenant_id).await?),
+            };
+        }
+
+        if let Some(code) = host_code {
+            tenant = match tenant {
+                Some(tenant) => Some(tenant.ensure_same(|tenant| tenant.code.eq_ignore_ascii_case(&code))?),
+                None => Some(Self::load_by_code(state, &code).await?),
+            };
+        }
+
+        Ok(tenant)
+    }
+
+    fn active(tenant: TenantRecord) -> Result<Self, AppError> {
+        if !tenant.is_active() {
+            return Err(AppError::AuthorizationError(format!("Tenant {} is not active", tenant.code)));
+        }
+
+        Ok(TenantContext { tenant_id: tenant.id, code: tenant.code, name: tenant.name })
+    }
+
+    fn ensure_same(self, same: impl FnOnce(&Self) -> bool) -> Result<Self, AppError> {
+        if !same(&self) {
+            return Err(AppError::AuthorizationError("Request refers to conflicting tenants".to_string()));
+        }
+        Ok(self)
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for TenantContext {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        // 优先使用租户中间件已解析的租户
+        if let Some(tenant) = parts.extensions.get::<TenantContext>() {
+            return Ok(tenant.clone());
+        }
+
+        // 携带令牌的请求以令牌中的租户为准
+        let user = match parts.extensions.get::<AuthenticatedUser>() {
+            Some(user) => Some(user.clone()),
+            None if parts.headers.contains_key(AUTHORIZATION) => Some(authenticate(&parts.headers, state).await?),
+            None => None,
+        };
+
+        Self::resolve(&parts.headers, user.as_ref(), state)
+            .await?
+            .ok_or_else(|| AppError::AuthenticationError("Tenant information is required".to_string()))
+    }
+}
+
+/// 从 X-Tenant-ID 头部读取租户ID
+fn tenant_id_from_headers(headers: &HeaderMap) -> Result<Option<Uuid>, AppError> {
+    let Some(value) = headers.get(TENANT_ID_HEADER) else {
+        return Ok(None);
+    };
+
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| Uuid::parse_str(value.trim()).ok())
+        .map(Some)
+        .ok_or_else(|| AppError::DomainError("Invalid X-Tenant-ID header".to_string()))
+}
+
+/// 从主机名中取出基础域名下一级的子域名作为租户编码
+fn tenant_code_from_host(headers: &HeaderMap, base_domain: Option<&str>) -> Option<String> {
+    let base_domain = base_domain?;
+    let host = headers.get(HOST)?.to_str().ok()?;
+    let host = host.split(':').next()?.to_ascii_lowercase();
+
+    let code = host.strip_suffix(base_domain)?.strip_suffix('.')?;
+    (!code.is_empty() && !code.contains('.')).then(|| code.to_string())
+}
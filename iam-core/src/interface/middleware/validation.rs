@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use crate::config::AppConfig;
 use crate::error::AppError;
+use crate::interface::middleware::tenant::TenantContext;
 
 /// 请求验证中间件
 pub async fn validation_middleware(
@@ -41,77 +42,24 @@ pub async fn validation_middleware(
     Ok(next.run(request).await)
 }
 
-/// 租户验证中间件
+/// 租户验证中间件，解析请求所属的租户并拒绝不存在或未启用的租户
 pub async fn tenant_validation_middleware(
-    State(_config): State<Arc<AppConfig>>,
+    tenant: TenantContext,
     mut request: Request,
     next: Next,
 ) -> Result<Response, AppError> {
-    // 从请求头中提取租户信息
-    let tenant_id = extract_tenant_from_request(&request)?;
-    
-    // 将租户ID添加到请求扩展中
-    request.extensions_mut().insert(tenant_id);
+    // 将租户添加到请求扩展中
+    request.extensions_mut().insert(tenant);
 
     Ok(next.run(request).await)
 }
 
-/// 从请求中提取租户ID
-fn extract_tenant_from_request(request: &Request) -> Result<uuid::Uuid, AppError> {
-    // 方法1: 从X-Tenant-ID头部获取
-    if let Some(tenant_header) = request.headers().get("X-Tenant-ID") {
-        if let Ok(tenant_str) = tenant_header.to_str() {
-            if let Ok(tenant_id) = uuid::Uuid::parse_str(tenant_str) {
-                return Ok(tenant_id);
-            }
-        }
-    }
-
-    // 方法2: 从子域名获取（如果使用子域名多租户）
-    if let Some(host) = request.headers().get("host") {
-        if let Ok(host_str) = host.to_str() {
-            if let Some(subdomain) = extract_subdomain(host_str) {
-                // 这里可以将子域名映射到租户ID
-                // 在实际应用中，你可能需要查询数据库来获取租户ID
-                if let Ok(tenant_id) = uuid::Uuid::parse_str(&subdomain) {
-                    return Ok(tenant_id);
-                }
-            }
-        }
-    }
-
-    // 方法3: 从JWT token中获取（如果用户已认证）
-    if let Some(auth_header) = request.headers().get("authorization") {
-        if let Ok(auth_str) = auth_header.to_str() {
-            if auth_str.starts_with("Bearer ") {
-                let _token = &auth_str[7..];
-                // 这里可以解析JWT token获取租户ID
-                // 为了简化，我们返回一个默认的租户ID
-                return Ok(uuid::Uuid::new_v4());
-            }
-        }
-    }
-
-    // 如果没有找到租户信息，返回错误
-    Err(AppError::AuthenticationError("Tenant information is required".to_string()))
-}
-
-/// 从主机名中提取子域名
-fn extract_subdomain(host: &str) -> Option<String> {
-    let parts: Vec<&str> = host.split('.').collect();
-    if parts.len() > 2 {
-        Some(parts[0].to_string())
-    } else {
-        None
-    }
-}
-
 /// 从请求扩展中获取租户ID
 pub fn extract_tenant_from_extensions(request: &Request) -> Result<uuid::Uuid, AppError> {
     request
         .extensions()
-        .get::<uuid::Uuid>()
-        .copied()
+        .get::<TenantContext>()
+        .map(|tenant| tenant.tenant_id)
         .ok_or_else(|| AppError::AuthenticationError("Tenant information not found".to_string()))
 }
 
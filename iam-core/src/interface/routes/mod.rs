@@ -1,19 +1,27 @@
 use axum::{
-    routing::{get, post},
+    middleware,
+    routing::{delete, get, post},
     Router,
 };
 use utoipa_swagger_ui::SwaggerUi;
 use utoipa::OpenApi;
 
-use crate::interface::handlers::{auth_handler, user_handler};
-use crate::interface::middleware::AppState;
+use crate::interface::handlers::{
+    admin_handler, auth_handler, authz_handler, organization_handler, permission_handler, role_handler, tenant_handler, user_handler,
+};
+use crate::interface::middleware::{authorization::route_authorization_middleware, AppState};
 use crate::openapi::{ApiDoc, health_check};
 
 /// 创建应用程序路由
 pub fn create_router(state: AppState) -> Router {
     Router::new()
         .route("/health", get(health_check))
-        .nest("/api/v1", create_api_routes())
+        .route("/.well-known/jwks.json", get(auth_handler::jwks))
+        // 按权限目录中 API 权限的路由授权，只作用于已注册的接口
+        .nest(
+            "/api/v1",
+            create_api_routes().route_layer(middleware::from_fn_with_state(state.clone(), route_authorization_middleware)),
+        )
         .merge(
             SwaggerUi::new("/swagger-ui")
                 .url("/api-docs/openapi.json", ApiDoc::openapi())
@@ -26,6 +34,11 @@ fn create_api_routes() -> Router<AppState> {
     Router::new()
         .nest("/auth", create_auth_routes())
         .nest("/users", create_user_routes())
+        .nest("/roles", create_role_routes())
+        .nest("/permissions", create_permission_routes())
+        .nest("/organizations", create_organization_routes())
+        .nest("/authz", create_authz_routes())
+        .nest("/admin", create_admin_routes())
 }
 
 /// 创建认证相关路由
@@ -34,6 +47,7 @@ fn create_auth_routes() -> Router<AppState> {
         .route("/login", post(auth_handler::login))
         .route("/refresh", post(auth_handler::refresh_token))
         .route("/logout", post(auth_handler::logout))
+        .route("/logout-all", post(auth_handler::logout_all))
 }
 
 /// 创建用户相关路由
@@ -41,7 +55,90 @@ fn create_user_routes() -> Router<AppState> {
     Router::new()
         .route("/", post(user_handler::register_user))
         .route("/", get(user_handler::list_users))
-        .route("/:id", get(user_handler::get_user))
+        .route(
+            "/:id",
+            get(user_handler::get_user)
+                .put(user_handler::update_user)
+                .patch(user_handler::patch_user),
+        )
+        .route("/:id/deactivate", post(user_handler::deactivate_user))
+        .route("/:id/reactivate", post(user_handler::reactivate_user))
+        .route("/:id/lock", post(user_handler::lock_user))
+        .route("/:id/unlock", post(user_handler::unlock_user))
+        .route("/:id/roles", post(role_handler::assign_user_role))
+        .route("/:id/roles/:role_id", delete(role_handler::remove_user_role))
+}
+
+/// 创建角色相关路由
+fn create_role_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(role_handler::list_roles).post(role_handler::create_role))
+        .route(
+            "/:id",
+            get(role_handler::get_role)
+                .put(role_handler::update_role)
+                .delete(role_handler::delete_role),
+        )
+        .route("/code/:code", get(role_handler::get_role_by_code))
+        .route(
+            "/:id/permissions",
+            get(role_handler::list_role_permissions).post(role_handler::grant_role_permission),
+        )
+        .route("/:id/permissions/:permission_id", delete(role_handler::revoke_role_permission))
+}
+
+/// 创建权限目录相关路由
+fn create_permission_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(permission_handler::list_permissions).post(permission_handler::create_permission))
+        .route(
+            "/:id",
+            get(permission_handler::get_permission)
+                .put(permission_handler::update_permission)
+                .delete(permission_handler::delete_permission),
+        )
+}
+
+/// 创建组织相关路由
+fn create_organization_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(organization_handler::get_organization_tree).post(organization_handler::create_organization),
+        )
+        .route(
+            "/:id",
+            get(organization_handler::get_organization_subtree)
+                .put(organization_handler::rename_organization)
+                .delete(organization_handler::delete_organization),
+        )
+        .route("/:id/move", post(organization_handler::move_organization))
+        .route(
+            "/:id/members",
+            get(organization_handler::list_organization_members).post(organization_handler::assign_user_organization),
+        )
+        .route("/:id/members/:user_id", delete(organization_handler::remove_user_organization))
+}
+
+/// 创建授权检查相关路由
+fn create_authz_routes() -> Router<AppState> {
+    Router::new()
+        .route("/check", post(authz_handler::check_access))
+        .route("/check/batch", post(authz_handler::check_access_batch))
+}
+
+/// 创建平台管理相关路由
+fn create_admin_routes() -> Router<AppState> {
+    Router::new()
+        .route("/projections", get(admin_handler::list_projections))
+        .route(
+            "/projections/:name/rebuild",
+            post(admin_handler::rebuild_projection).get(admin_handler::get_rebuild_status),
+        )
+        .route("/tenants", get(tenant_handler::list_tenants).post(tenant_handler::create_tenant))
+        .route("/tenants/:id", get(tenant_handler::get_tenant).put(tenant_handler::rename_tenant))
+        .route("/tenants/:id/suspend", post(tenant_handler::suspend_tenant))
+        .route("/tenants/:id/reactivate", post(tenant_handler::reactivate_tenant))
 }
 
 // 健康检查端点现在在 openapi 模块中定义
@@ -1,12 +1,26 @@
 use iam_core::{
-    application::services::{UserService, QueryService},
+    application::services::{
+        AuthorizationService, LoginGuard, LoginProtectionPolicy, OrganizationService, PermissionService,
+        PlatformAdminAccount, PlatformBootstrap, PolicyDecisionService, QueryService, RefreshTokenService, RoleService,
+        RoutePermissionService, TenantService, UserService,
+    },
     config::AppConfig,
-    infrastructure::persistence::SqlxEventStore,
+    domain::identity_access::commands::CreateTenantCommand,
+    infrastructure::persistence::{
+        EffectivePermissionProjector, EventMetadata, OrganizationMemberProjector, OrganizationProjector, PermissionProjector,
+        ProjectionRebuilder, ProjectionRunner, RebuildMode, RetryPolicy, RolePermissionProjector, RoleProjector,
+        SnapshotPolicy, SqlxApiPermissionStore, SqlxCheckpointStore, SqlxEffectivePermissionStore, SqlxEventStore, SqlxLoginAttemptStore,
+        SqlxRefreshTokenStore, SqlxSnapshotStore, SqlxTenantStore, SqlxTokenRevocationStore, TenantProjector,
+        UserProjector,
+    },
+    infrastructure::security::KeyRing,
     interface::{middleware::AppState, routes::create_router},
 };
 use sea_orm::Database;
 use sqlx::MySqlPool;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tower_http::cors::CorsLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -26,6 +40,10 @@ async fn main() -> anyhow::Result<()> {
     let config = AppConfig::from_env()
         .map_err(|e| anyhow::anyhow!("Failed to load configuration: {}", e))?;
 
+    // 解析子命令，未指定时启动服务器
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = Command::parse(&args)?;
+
     tracing::info!("Starting IAM Core server with config: {:?}", config);
 
     // 连接数据库
@@ -40,12 +58,143 @@ async fn main() -> anyhow::Result<()> {
 
     // 初始化服务
     let event_store = Arc::new(SqlxEventStore::new(pool.clone()));
-    let user_service = Arc::new(UserService::new(event_store.clone()));
-    let query_service = Arc::new(QueryService::new(db_conn));
+    let snapshot_store = Arc::new(SqlxSnapshotStore::new(pool.clone()));
+    let snapshot_policy = SnapshotPolicy::new(snapshot_store, config.snapshot.frequency);
+    let retry_policy = RetryPolicy::new(
+        config.retry.max_attempts,
+        Duration::from_millis(config.retry.backoff_ms),
+        Duration::from_millis(config.retry.max_backoff_ms),
+    );
+    let user_service = Arc::new(
+        UserService::new(event_store.clone())
+            .with_snapshots(snapshot_policy.clone())
+            .with_retry(retry_policy),
+    );
+    let role_service = Arc::new(
+        RoleService::new(event_store.clone())
+            .with_snapshots(snapshot_policy.clone())
+            .with_retry(retry_policy),
+    );
+    let tenant_service = Arc::new(
+        TenantService::new(event_store.clone())
+            .with_snapshots(snapshot_policy.clone())
+            .with_retry(retry_policy),
+    );
+    let organization_service = Arc::new(
+        OrganizationService::new(event_store.clone())
+            .with_snapshots(snapshot_policy.clone())
+            .with_retry(retry_policy),
+    );
+    let permission_service = Arc::new(
+        PermissionService::new(event_store.clone())
+            .with_snapshots(snapshot_policy)
+            .with_retry(retry_policy),
+    );
+    let tenants = Arc::new(SqlxTenantStore::new(pool.clone()));
+    let effective_permissions = Arc::new(SqlxEffectivePermissionStore::new(pool.clone()));
+    let authorization_service = Arc::new(AuthorizationService::new(effective_permissions.clone()));
+    let route_permissions = Arc::new(
+        RoutePermissionService::new(Arc::new(SqlxApiPermissionStore::new(pool.clone())))
+            .with_exempt_routes(&config.authorization.exempt_routes)
+            .with_cache_ttl(Duration::from_secs(config.authorization.route_cache_secs)),
+    );
+    let policy_decisions = Arc::new(
+        PolicyDecisionService::new(user_service.clone(), tenants.clone(), effective_permissions).with_cache(
+            Duration::from_secs(config.authorization.decision_cache_secs),
+            config.authorization.decision_cache_capacity,
+        ),
+    );
+    let query_service = Arc::new(QueryService::new(db_conn.clone()));
+    let login_guard = Arc::new(LoginGuard::new(
+        Arc::new(SqlxLoginAttemptStore::new(pool.clone())),
+        user_service.clone(),
+        LoginProtectionPolicy {
+            max_failed_per_user: config.login.max_failed_attempts_per_user,
+            max_failed_per_ip: config.login.max_failed_attempts_per_ip,
+            window: Duration::from_secs(config.login.attempt_window_secs),
+            base_lockout: Duration::from_secs(config.login.lockout_base_secs),
+            max_lockout: Duration::from_secs(config.login.lockout_max_secs),
+        },
+    ));
+    let refresh_tokens = Arc::new(RefreshTokenService::new(
+        Arc::new(SqlxRefreshTokenStore::new(pool.clone())),
+        user_service.clone(),
+        Duration::from_secs(config.jwt.refresh_expiration_hours * 3600),
+    ));
+
+    let projection_runner = ProjectionRunner::new(event_store.clone(), Arc::new(SqlxCheckpointStore::new(pool.clone())))
+        .with_projector(Arc::new(UserProjector::new(db_conn.clone())))
+        .with_projector(Arc::new(RoleProjector::new(db_conn.clone())))
+        .with_projector(Arc::new(TenantProjector::new(db_conn.clone())))
+        .with_projector(Arc::new(OrganizationProjector::new(db_conn.clone())))
+        .with_projector(Arc::new(OrganizationMemberProjector::new(db_conn.clone())))
+        .with_projector(Arc::new(PermissionProjector::new(db_conn.clone())))
+        .with_projector(Arc::new(RolePermissionProjector::new(db_conn.clone())))
+        .with_projector(Arc::new(EffectivePermissionProjector::new(db_conn)))
+        .with_batch_size(config.projection.batch_size)
+        .with_poll_interval(Duration::from_millis(config.projection.poll_interval_ms))
+        .with_gap_timeout(Duration::from_millis(config.projection.gap_timeout_ms))
+        .with_skipped_retention(Duration::from_millis(config.projection.skipped_retention_ms));
+    let projection_rebuilder = Arc::new(projection_runner.rebuilder());
+
+    // 平台租户与其他租户一样由事件创建，重建投影后仍然存在
+    if let Some(platform_tenant_id) = config.platform.tenant_id {
+        let command = CreateTenantCommand {
+            name: config.platform.tenant_name.clone(),
+            code: config.platform.tenant_code.clone(),
+        };
+        if tenant_service.ensure_tenant(platform_tenant_id, command, &EventMetadata::default()).await? {
+            tracing::info!("Created platform tenant {}", platform_tenant_id);
+        }
+
+        // 平台管理员由 platform:admin 权限决定，第一个平台管理员按配置创建
+        if let Some(admin) = &config.platform.admin {
+            let account = PlatformAdminAccount {
+                username: admin.username.clone(),
+                email: admin.email.clone(),
+                password_hash: bcrypt::hash(&admin.password, bcrypt::DEFAULT_COST)?,
+            };
+            let bootstrap = PlatformBootstrap::new(event_store.clone());
+            if bootstrap.ensure_admin(platform_tenant_id, account, &EventMetadata::default()).await? {
+                tracing::info!("Created platform administrator {}", admin.username);
+            }
+        }
+    }
+
+    if let Command::RebuildProjection { name, mode } = command {
+        return rebuild_projection(&projection_rebuilder, &name, mode).await;
+    }
+
+    // 加载令牌签名密钥，使用密钥目录时定期重新加载以发布和启用轮换的密钥
+    let key_ring = Arc::new(KeyRing::from_config(&config.jwt)?);
+    if config.jwt.keys_dir.is_some() {
+        key_ring.clone().spawn_reload(Duration::from_secs(config.jwt.key_reload_interval_secs));
+    }
+
+    // 启动投影后台任务，持续把事件投影到读模型
+    projection_runner.spawn();
     let config = Arc::new(config);
 
     // 创建应用状态
-    let app_state = AppState::new(user_service, query_service, event_store, config.clone());
+    let app_state = AppState::new(
+        user_service,
+        role_service,
+        tenant_service,
+        organization_service,
+        permission_service,
+        authorization_service,
+        route_permissions,
+        policy_decisions,
+        query_service,
+        login_guard,
+        refresh_tokens,
+        Arc::new(SqlxTokenRevocationStore::new(pool.clone())),
+        key_ring,
+        tenants,
+        event_store,
+        projection_rebuilder,
+        config.clone(),
+    );
 
     // 创建路由
     let app = create_router(app_state).layer(CorsLayer::permissive());
@@ -60,7 +209,54 @@ async fn main() -> anyhow::Result<()> {
         config.server.port
     );
 
-    axum::serve(listener, app).await?;
+    // 保留连接地址，事件元数据在没有代理头部时用它作为客户端IP
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
+
+    Ok(())
+}
+
+/// 命令行子命令
+enum Command {
+    /// 启动HTTP服务器
+    Serve,
+    /// 从事件存储重建投影：rebuild-projection <name> [--in-place]
+    RebuildProjection { name: String, mode: RebuildMode },
+}
+
+impl Command {
+    fn parse(args: &[String]) -> anyhow::Result<Self> {
+        match args.first().map(String::as_str) {
+            None | Some("serve") => Ok(Command::Serve),
+            Some("rebuild-projection") => {
+                let name = args
+                    .get(1)
+                    .filter(|name| !name.starts_with("--"))
+                    .ok_or_else(|| anyhow::anyhow!("Usage: iam-core rebuild-projection <name> [--in-place]"))?;
+                // 默认重建到影子表后替换，运行中的服务读取不受影响
+                let mode = if args[2..].iter().any(|arg| arg == "--in-place") {
+                    RebuildMode::InPlace
+                } else {
+                    RebuildMode::Shadow
+                };
+                Ok(Command::RebuildProjection { name: name.clone(), mode })
+            }
+            Some(other) => Err(anyhow::anyhow!("Unknown command: {}", other)),
+        }
+    }
+}
+
+/// 执行投影重建并输出结果
+///
+/// 重建只与本进程内的投影任务协调，运行中的服务请改用管理接口
+async fn rebuild_projection(rebuilder: &ProjectionRebuilder, name: &str, mode: RebuildMode) -> anyhow::Result<()> {
+    let status = rebuilder.rebuild(name, mode).await?;
+
+    tracing::info!(
+        "Projection {} rebuilt: {} events replayed up to position {}",
+        status.projection,
+        status.events_replayed,
+        status.position
+    );
 
     Ok(())
 }
@@ -1,6 +1,9 @@
 use utoipa::OpenApi;
 
-use crate::interface::handlers::{auth_handler, user_handler};
+use crate::interface::handlers::{
+    admin_handler, auth_handler, authz_handler, organization_handler, permission_handler, role_handler, tenant_handler,
+    user_handler,
+};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -8,9 +11,53 @@ use crate::interface::handlers::{auth_handler, user_handler};
         user_handler::register_user,
         user_handler::get_user,
         user_handler::list_users,
+        user_handler::update_user,
+        user_handler::patch_user,
+        user_handler::deactivate_user,
+        user_handler::reactivate_user,
+        user_handler::lock_user,
+        user_handler::unlock_user,
+        role_handler::list_roles,
+        role_handler::get_role,
+        role_handler::get_role_by_code,
+        role_handler::create_role,
+        role_handler::update_role,
+        role_handler::delete_role,
+        role_handler::assign_user_role,
+        role_handler::remove_user_role,
+        role_handler::list_role_permissions,
+        role_handler::grant_role_permission,
+        role_handler::revoke_role_permission,
+        permission_handler::list_permissions,
+        permission_handler::get_permission,
+        permission_handler::create_permission,
+        permission_handler::update_permission,
+        permission_handler::delete_permission,
+        organization_handler::get_organization_tree,
+        organization_handler::get_organization_subtree,
+        organization_handler::create_organization,
+        organization_handler::rename_organization,
+        organization_handler::move_organization,
+        organization_handler::delete_organization,
+        organization_handler::list_organization_members,
+        organization_handler::assign_user_organization,
+        organization_handler::remove_user_organization,
+        authz_handler::check_access,
+        authz_handler::check_access_batch,
         auth_handler::login,
         auth_handler::refresh_token,
         auth_handler::logout,
+        auth_handler::logout_all,
+        auth_handler::jwks,
+        admin_handler::list_projections,
+        admin_handler::rebuild_projection,
+        admin_handler::get_rebuild_status,
+        tenant_handler::list_tenants,
+        tenant_handler::get_tenant,
+        tenant_handler::create_tenant,
+        tenant_handler::rename_tenant,
+        tenant_handler::suspend_tenant,
+        tenant_handler::reactivate_tenant,
         health_check
     ),
     components(
@@ -18,16 +65,56 @@ use crate::interface::handlers::{auth_handler, user_handler};
             user_handler::RegisterUserRequest,
             user_handler::UserResponse,
             user_handler::RegisterUserResponse,
+            user_handler::UpdateUserRequest,
+            user_handler::UserStatusChangeRequest,
+            user_handler::LockUserRequest,
+            role_handler::CreateRoleRequest,
+            role_handler::CreateRoleResponse,
+            role_handler::UpdateRoleRequest,
+            role_handler::AssignUserRoleRequest,
+            role_handler::GrantRolePermissionRequest,
+            role_handler::RoleResponse,
+            permission_handler::CreatePermissionRequest,
+            permission_handler::CreatePermissionResponse,
+            permission_handler::UpdatePermissionRequest,
+            permission_handler::PermissionResponse,
+            organization_handler::CreateOrganizationRequest,
+            organization_handler::CreateOrganizationResponse,
+            organization_handler::RenameOrganizationRequest,
+            organization_handler::MoveOrganizationRequest,
+            organization_handler::AssignUserOrganizationRequest,
+            organization_handler::OrganizationNode,
+            authz_handler::CheckAccessRequest,
+            authz_handler::BatchCheckAccessRequest,
+            authz_handler::GrantResponse,
+            authz_handler::CheckAccessResponse,
+            authz_handler::BatchCheckAccessResponse,
             auth_handler::LoginRequest,
             auth_handler::LoginResponse,
+            auth_handler::RefreshTokenRequest,
+            auth_handler::LogoutRequest,
             auth_handler::TokenInfo,
+            admin_handler::RebuildModeRequest,
+            admin_handler::RebuildProjectionRequest,
+            admin_handler::RebuildStatusResponse,
+            admin_handler::ProjectionResponse,
+            tenant_handler::CreateTenantRequest,
+            tenant_handler::CreateTenantResponse,
+            tenant_handler::RenameTenantRequest,
+            tenant_handler::TenantStatusChangeRequest,
+            tenant_handler::TenantResponse,
             HealthResponse,
             ErrorResponse
         )
     ),
     tags(
         (name = "users", description = "用户管理相关接口"),
+        (name = "roles", description = "角色管理相关接口"),
+        (name = "permissions", description = "权限目录相关接口"),
+        (name = "organizations", description = "组织架构相关接口"),
+        (name = "authz", description = "供其他服务调用的授权检查接口"),
         (name = "auth", description = "认证相关接口"),
+        (name = "admin", description = "平台管理相关接口"),
         (name = "system", description = "系统相关接口")
     ),
     info(